@@ -0,0 +1,19 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/engine/dawg.rs"]
+mod dawg;
+
+fn main() {
+    println!("cargo:rerun-if-changed=assets/sowpods.txt");
+    println!("cargo:rerun-if-changed=src/engine/dawg.rs");
+
+    let words = fs::read_to_string("assets/sowpods.txt").expect("assets/sowpods.txt is missing");
+    let words: Vec<&[u8]> = words.lines().map(|word| word.trim().as_bytes()).collect();
+    let dawg = dawg::Dawg::build(&words);
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("sowpods.dawg");
+    fs::write(out, dawg.to_bytes()).unwrap();
+}
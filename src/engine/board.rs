@@ -0,0 +1,219 @@
+use super::moves::*;
+use super::player::*;
+use super::score::*;
+use super::tile::*;
+
+pub const TRIPLE_WORD: &[(u8, u8)] = &[
+    (0, 0),
+    (0, 7),
+    (0, 14),
+    (7, 0),
+    (7, 14),
+    (14, 0),
+    (14, 7),
+    (14, 14),
+];
+
+pub const DOUBLE_LETTER: &[(u8, u8)] = &[
+    (0, 3),
+    (0, 11),
+    (3, 0),
+    (3, 14),
+    (11, 0),
+    (11, 14),
+    (14, 3),
+    (14, 11),
+    (2, 6),
+    (3, 7),
+    (2, 8),
+    (12, 6),
+    (11, 7),
+    (12, 8),
+    (6, 2),
+    (7, 3),
+    (8, 2),
+    (6, 12),
+    (7, 11),
+    (8, 12),
+    (8, 8),
+    (6, 6),
+    (8, 6),
+    (6, 8),
+];
+
+pub const TRIPLE_LETTER: &[(u8, u8)] = &[
+    (1, 5),
+    (1, 9),
+    (5, 5),
+    (5, 9),
+    (9, 5),
+    (9, 9),
+    (13, 5),
+    (13, 9),
+    (5, 1),
+    (9, 1),
+    (5, 13),
+    (9, 13),
+];
+
+pub const DOUBLE_WORD: &[(u8, u8)] = &[
+    (1, 1),
+    (1, 13),
+    (13, 1),
+    (13, 13),
+    (2, 2),
+    (2, 12),
+    (12, 2),
+    (12, 12),
+    (11, 3),
+    (11, 11),
+    (3, 3),
+    (3, 11),
+    (10, 4),
+    (10, 10),
+    (4, 4),
+    (4, 10),
+];
+
+pub type Grid = [[Option<PlacedTile>; 15]; 15];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Premium {
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+}
+
+pub fn premium(row: usize, col: usize) -> Option<Premium> {
+    let square = (row as u8, col as u8);
+
+    // The center star doubles the opening word.
+    if square == (7, 7) || DOUBLE_WORD.contains(&square) {
+        Some(Premium::DoubleWord)
+    } else if TRIPLE_WORD.contains(&square) {
+        Some(Premium::TripleWord)
+    } else if DOUBLE_LETTER.contains(&square) {
+        Some(Premium::DoubleLetter)
+    } else if TRIPLE_LETTER.contains(&square) {
+        Some(Premium::TripleLetter)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone)]
+pub struct Board {
+    squares: Grid,
+    pub placed: Vec<(usize, usize)>,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Board {
+    pub fn new() -> Board {
+        Board {
+            squares: [[None; 15]; 15],
+            placed: vec![],
+        }
+    }
+
+    pub fn squares(&self) -> &Grid {
+        &self.squares
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<PlacedTile> {
+        self.squares[row][col]
+    }
+
+    pub fn is_placed(&self, row: usize, col: usize) -> bool {
+        self.placed.contains(&(row, col))
+    }
+
+    /// Puts a rack tile on an empty square for this turn's move.
+    pub fn place(&mut self, row: usize, col: usize, tile: Tile) -> bool {
+        if self.squares[row][col].is_some() {
+            return false;
+        }
+
+        self.squares[row][col] = Some(PlacedTile::new(tile));
+        self.placed.push((row, col));
+        true
+    }
+
+    /// Swaps two tiles placed this turn.
+    pub fn swap_placed(&mut self, a: (usize, usize), b: (usize, usize)) -> bool {
+        if !self.is_placed(a.0, a.1) || !self.is_placed(b.0, b.1) {
+            return false;
+        }
+
+        let tmp = self.squares[a.0][a.1];
+        self.squares[a.0][a.1] = self.squares[b.0][b.1];
+        self.squares[b.0][b.1] = tmp;
+        true
+    }
+
+    /// The square of a blank placed this turn that has no letter yet.
+    pub fn unassigned_blank(&self) -> Option<(usize, usize)> {
+        self.placed
+            .iter()
+            .copied()
+            .find(|&(row, col)| self.squares[row][col] == Some(PlacedTile::Blank(Tile::Blank)))
+    }
+
+    pub fn assign_blank(&mut self, row: usize, col: usize, letter: Tile) {
+        if !self.is_placed(row, col) {
+            return;
+        }
+
+        if let Some(PlacedTile::Blank(_)) = self.squares[row][col] {
+            self.squares[row][col] = Some(PlacedTile::Blank(letter));
+        }
+    }
+
+    pub fn take_back(&mut self, row: usize, col: usize, player: &mut Player) {
+        if !self.is_placed(row, col) {
+            return;
+        }
+
+        if let Some(tile) = self.squares[row][col].take() {
+            player.add_tile(tile.rack_tile());
+        }
+        self.placed.retain(|&square| square != (row, col));
+    }
+
+    pub fn recall(&mut self, player: &mut Player) {
+        for (row, col) in self.placed.drain(..) {
+            if let Some(tile) = self.squares[row][col].take() {
+                player.add_tile(tile.rack_tile());
+            }
+        }
+    }
+
+    pub fn validate_move(&self) -> Result<Direction, MoveError> {
+        validate(&self.squares, &self.placed)
+    }
+
+    pub fn formed_words(&self, direction: Direction) -> Vec<Word> {
+        formed_words(&self.squares, &self.placed, direction)
+    }
+
+    pub fn score_move(&self, words: &[Word]) -> MoveScore {
+        score_move(&self.squares, words, &self.placed)
+    }
+
+    pub fn commit(&mut self) {
+        self.placed.clear();
+    }
+
+    /// Takes a committed tile back off the board, e.g. when a phony is
+    /// challenged.
+    pub fn lift(&mut self, row: usize, col: usize) -> Option<PlacedTile> {
+        self.placed.retain(|&square| square != (row, col));
+        self.squares[row][col].take()
+    }
+}
@@ -0,0 +1,286 @@
+use ::rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt;
+
+use super::endgame::*;
+use super::leave::*;
+use super::movegen::*;
+use super::state::*;
+use super::turn::MIN_BAG_FOR_EXCHANGE;
+
+/// Longest word a beginner bot will play.
+pub const BEGINNER_MAX_LEN: usize = 5;
+/// How many of the best moves an intermediate bot picks from.
+pub const INTERMEDIATE_TOP_N: usize = 5;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Level {
+    /// Only plays words of up to `BEGINNER_MAX_LEN` letters, picking at
+    /// random among the lower scoring half of those moves.
+    Beginner,
+    /// Picks at random among the `INTERMEDIATE_TOP_N` best moves.
+    #[default]
+    Intermediate,
+    /// Plays the move with the best score plus leave value, and searches
+    /// the endgame for up to `ENDGAME_TIME` once the bag is empty.
+    Expert,
+}
+
+impl Level {
+    pub fn next(&self) -> Level {
+        match self {
+            Level::Beginner => Level::Intermediate,
+            Level::Intermediate => Level::Expert,
+            Level::Expert => Level::Beginner,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Level::Beginner => write!(f, "Beginner"),
+            Level::Intermediate => write!(f, "Intermediate"),
+            Level::Expert => write!(f, "Expert"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Decision {
+    Play(Candidate),
+    Exchange(Vec<usize>),
+    Pass,
+    /// Challenge the last play, which the bot only does for phonies.
+    Challenge,
+    /// Let a valid play that used the last tiles end the game.
+    Accept,
+}
+
+/// A computer player. Its random choices come from its own seed so seeded
+/// games play out the same way, unless an expert's endgame search runs out
+/// of time.
+#[derive(Clone)]
+pub struct Bot {
+    pub level: Level,
+    /// Values the tiles kept after a move for `Level::Expert`.
+    pub leaves: LeaveTable,
+    rng: StdRng,
+}
+
+impl Bot {
+    pub fn new(level: Level, seed: u64) -> Bot {
+        Bot {
+            level,
+            leaves: LeaveTable::standard(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Every legal move for the current player, best first.
+    pub fn candidates(&self, state: &State) -> Vec<Candidate> {
+        ranked(state, usize::MAX)
+    }
+
+    /// The score of `candidate` plus the value of the tiles it leaves on
+    /// the rack. Leaves are worth nothing once the bag is empty.
+    pub fn equity(&self, state: &State, candidate: &Candidate) -> f32 {
+        let score = candidate.score.total as f32;
+        if state.tile_bag.is_empty() {
+            return score;
+        }
+
+        score + self.leaves.value(&candidate.leave(&state.player().tiles))
+    }
+
+    pub fn decide(&mut self, state: &State) -> Decision {
+        if state.phonies().is_some_and(|phony| !phony.is_empty()) {
+            return Decision::Challenge;
+        }
+        if state.going_out().is_some() {
+            return Decision::Accept;
+        }
+
+        if self.level == Level::Expert {
+            let limits = EndgameLimits {
+                time: Some(ENDGAME_TIME),
+                ..EndgameLimits::default()
+            };
+            let solution = solve_endgame(state, limits);
+            match solution.and_then(|solution| solution.line.into_iter().next()) {
+                Some(EndgameMove::Play(candidate)) => return Decision::Play(candidate),
+                Some(EndgameMove::Pass) => return Decision::Pass,
+                None => {}
+            }
+        }
+
+        let mut candidates = self.candidates(state);
+
+        let choice = match self.level {
+            Level::Beginner => {
+                let mut short: Vec<Candidate> = candidates.into_iter().filter(is_short).collect();
+                let half = short.len() / 2;
+                if short.is_empty() {
+                    None
+                } else {
+                    Some(short.swap_remove(self.rng.gen_range(half..short.len())))
+                }
+            }
+            Level::Intermediate if !candidates.is_empty() => {
+                let top = candidates.len().min(INTERMEDIATE_TOP_N);
+                Some(candidates.swap_remove(self.rng.gen_range(0..top)))
+            }
+            Level::Intermediate => None,
+            Level::Expert => candidates
+                .into_iter()
+                .map(|c| (self.equity(state, &c), c))
+                .max_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(_, c)| c),
+        };
+
+        match choice {
+            Some(candidate) => Decision::Play(candidate),
+            None if state.tile_bag.len() >= MIN_BAG_FOR_EXCHANGE => {
+                Decision::Exchange((0..state.player().tiles.len()).collect())
+            }
+            None => Decision::Pass,
+        }
+    }
+}
+
+/// Whether every word `candidate` forms is short enough for a beginner.
+fn is_short(candidate: &Candidate) -> bool {
+    candidate
+        .score
+        .words
+        .iter()
+        .all(|w| w.word.len() <= BEGINNER_MAX_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::testing::*;
+    use crate::engine::tile::Tile;
+    use crate::engine::turn::{Action, ChallengeRule};
+
+    #[test]
+    fn experts_play_the_best_equity() {
+        let state = State::new(Some(2));
+        let mut bot = Bot::new(Level::Expert, 2);
+        let candidates = bot.candidates(&state);
+
+        assert!(candidates
+            .windows(2)
+            .all(|pair| pair[0].score.total >= pair[1].score.total));
+        let best = candidates
+            .iter()
+            .map(|c| bot.equity(&state, c))
+            .fold(f32::MIN, f32::max);
+        match bot.decide(&state) {
+            Decision::Play(candidate) => assert_eq!(bot.equity(&state, &candidate), best),
+            decision => panic!("expected a play, got {:?}", decision),
+        }
+    }
+
+    #[test]
+    fn equity_counts_the_leave_until_the_bag_is_empty() {
+        let mut state = State::new(Some(2));
+        let mut bot = Bot::new(Level::Expert, 2);
+        bot.leaves = LeaveTable::parse("").unwrap();
+        let rack = state.player().tiles.len();
+        let candidate = bot
+            .candidates(&state)
+            .into_iter()
+            .find(|c| c.tiles.len() < rack)
+            .unwrap();
+        let score = candidate.score.total as f32;
+        assert_eq!(bot.equity(&state, &candidate), score);
+
+        let leave: String = candidate
+            .leave(&state.player().tiles)
+            .iter()
+            .map(|t| match t {
+                Tile::Blank => "?",
+                t => t.into(),
+            })
+            .collect();
+        bot.leaves = LeaveTable::parse(&format!("{} 4", leave)).unwrap();
+        assert_eq!(bot.equity(&state, &candidate), score + 4.0);
+
+        state.tile_bag.clear();
+        assert_eq!(bot.equity(&state, &candidate), score);
+    }
+
+    #[test]
+    fn intermediates_pick_among_the_best_moves() {
+        let state = State::new(Some(2));
+        let candidates = Bot::new(Level::Intermediate, 0).candidates(&state);
+        let top = &candidates[..INTERMEDIATE_TOP_N];
+
+        for seed in 0..10 {
+            match Bot::new(Level::Intermediate, seed).decide(&state) {
+                Decision::Play(candidate) => assert!(top.contains(&candidate)),
+                decision => panic!("expected a play, got {:?}", decision),
+            }
+        }
+    }
+
+    #[test]
+    fn beginners_play_low_scoring_short_words() {
+        let mut state = bot_game(4, 6);
+        let mut bot = Bot::new(Level::Beginner, 4);
+
+        for _ in 0..10 {
+            if state.finished {
+                break;
+            }
+            let short: Vec<Candidate> = bot
+                .candidates(&state)
+                .into_iter()
+                .filter(is_short)
+                .collect();
+
+            match bot.decide(&state) {
+                Decision::Play(candidate) => {
+                    assert!(candidate
+                        .score
+                        .words
+                        .iter()
+                        .all(|w| w.word.len() <= BEGINNER_MAX_LEN));
+                    assert!(candidate.score.total <= short[short.len() / 2].score.total);
+                    assert!(state.place_move(&candidate.tiles));
+                    state.act(Action::Submit).unwrap();
+                }
+                _ => {
+                    assert!(short.is_empty());
+                    state.act(Action::Pass).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn challenges_phonies() {
+        let mut state = State::new(Some(9));
+        state.challenge_rule = ChallengeRule::Single;
+        deal(&mut state, 0, "QZEEEEE");
+        place(&mut state, 7, 7, "qz");
+        state.act(Action::Submit).unwrap();
+
+        assert_eq!(
+            Bot::new(Level::Beginner, 9).decide(&state),
+            Decision::Challenge
+        );
+    }
+
+    #[test]
+    fn levels_cycle() {
+        assert_eq!(Level::default(), Level::Intermediate);
+        assert_eq!(Level::Beginner.next().next().next(), Level::Beginner);
+    }
+
+    #[test]
+    fn finishes_a_game() {
+        assert!(play_until(3, |_| false).finished);
+    }
+}
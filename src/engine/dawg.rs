@@ -0,0 +1,216 @@
+//! A minimized word graph over the letters `a` to `z`.
+//!
+//! This file only depends on `std` because `build.rs` includes it to
+//! precompile the word list.
+
+use std::collections::HashMap;
+
+const MAGIC: &[u8; 4] = b"DAWG";
+const VERSION: u32 = 1;
+
+const TERMINAL: u32 = 1 << 31;
+const COUNT_SHIFT: u32 = 26;
+const COUNT_MASK: u32 = 0x1f;
+const INDEX_MASK: u32 = (1 << COUNT_SHIFT) - 1;
+const LETTER_SHIFT: u32 = 27;
+
+/// Nodes pack `terminal | edge count | first edge`, edges pack
+/// `letter | target node`.
+pub struct Dawg {
+    root: u32,
+    nodes: Vec<u32>,
+    edges: Vec<u32>,
+}
+
+impl Dawg {
+    /// Builds the graph from lowercase ASCII words, merging every shared
+    /// suffix into a single node.
+    pub fn build(words: &[&[u8]]) -> Dawg {
+        let mut words: Vec<&[u8]> = words
+            .iter()
+            .copied()
+            .filter(|word| !word.is_empty() && word.iter().all(u8::is_ascii_lowercase))
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+
+        let mut dawg = Dawg {
+            root: 0,
+            nodes: vec![],
+            edges: vec![],
+        };
+        let mut registry = HashMap::new();
+        dawg.root = dawg.add(&words, 0, &mut registry);
+        dawg
+    }
+
+    /// Adds the node for the common prefix of `words`, which are sorted and
+    /// all at least `depth` letters long.
+    fn add(
+        &mut self,
+        words: &[&[u8]],
+        depth: usize,
+        registry: &mut HashMap<(bool, Vec<u32>), u32>,
+    ) -> u32 {
+        let terminal = words.first().is_some_and(|word| word.len() == depth);
+        let rest = if terminal { &words[1..] } else { words };
+
+        let mut edges = vec![];
+        let mut start = 0;
+        for i in 1..=rest.len() {
+            if i == rest.len() || rest[i][depth] != rest[start][depth] {
+                let letter = (rest[start][depth] - b'a') as u32;
+                let target = self.add(&rest[start..i], depth + 1, registry);
+                edges.push(letter << LETTER_SHIFT | target);
+                start = i;
+            }
+        }
+
+        let key = (terminal, edges);
+        if let Some(&node) = registry.get(&key) {
+            return node;
+        }
+
+        let mut node = (self.edges.len() as u32) | (key.1.len() as u32) << COUNT_SHIFT;
+        if terminal {
+            node |= TERMINAL;
+        }
+        self.edges.extend_from_slice(&key.1);
+        self.nodes.push(node);
+
+        let index = self.nodes.len() as u32 - 1;
+        registry.insert(key, index);
+        index
+    }
+
+    pub fn root(&self) -> u32 {
+        self.root
+    }
+
+    pub fn is_terminal(&self, node: u32) -> bool {
+        self.nodes[node as usize] & TERMINAL != 0
+    }
+
+    /// The `(letter, node)` pairs leaving `node`, with letters from 0 for
+    /// `a` to 25 for `z`.
+    pub fn children(&self, node: u32) -> impl Iterator<Item = (u8, u32)> + '_ {
+        let node = self.nodes[node as usize];
+        let start = (node & INDEX_MASK) as usize;
+        let count = ((node >> COUNT_SHIFT) & COUNT_MASK) as usize;
+
+        self.edges[start..start + count]
+            .iter()
+            .map(|&edge| ((edge >> LETTER_SHIFT) as u8, edge & INDEX_MASK))
+    }
+
+    pub fn child(&self, node: u32, letter: u8) -> Option<u32> {
+        self.children(node)
+            .find(|&(l, _)| l == letter)
+            .map(|(_, target)| target)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        let header = [
+            VERSION,
+            self.root,
+            self.nodes.len() as u32,
+            self.edges.len() as u32,
+        ];
+
+        for value in header.iter().chain(&self.nodes).chain(&self.edges) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Dawg> {
+        if bytes.get(..4)? != MAGIC {
+            return None;
+        }
+
+        let mut values = bytes[4..]
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+
+        if values.next()? != VERSION {
+            return None;
+        }
+        let root = values.next()?;
+        let node_count = values.next()? as usize;
+        let edge_count = values.next()? as usize;
+
+        let nodes: Vec<u32> = values.by_ref().take(node_count).collect();
+        let edges: Vec<u32> = values.take(edge_count).collect();
+        if nodes.len() != node_count || edges.len() != edge_count {
+            return None;
+        }
+
+        Some(Dawg { root, nodes, edges })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(words: &[&str]) -> Dawg {
+        let words: Vec<&[u8]> = words.iter().map(|word| word.as_bytes()).collect();
+        Dawg::build(&words)
+    }
+
+    fn contains(dawg: &Dawg, word: &str) -> bool {
+        word.bytes()
+            .try_fold(dawg.root(), |node, letter| dawg.child(node, letter - b'a'))
+            .is_some_and(|node| dawg.is_terminal(node))
+    }
+
+    #[test]
+    fn holds_exactly_its_words() {
+        let dawg = build(&["car", "cars", "cat", "cats", "do", "dog"]);
+
+        for word in ["car", "cars", "cat", "cats", "do", "dog"] {
+            assert!(contains(&dawg, word), "{}", word);
+        }
+        for word in ["c", "ca", "d", "dogs", "cog", "a"] {
+            assert!(!contains(&dawg, word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn skips_invalid_words() {
+        let dawg = build(&["", "Cat", "c-t", "cat", "cat"]);
+        assert!(contains(&dawg, "cat"));
+        assert_eq!(dawg.children(dawg.root()).count(), 1);
+    }
+
+    #[test]
+    fn merges_shared_suffixes() {
+        // c and r lead to one node for "ats".
+        let dawg = build(&["cats", "rats"]);
+        assert_eq!(dawg.nodes.len(), 5);
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let dawg = build(&["car", "cars", "cat", "do", "dog"]);
+        let loaded = Dawg::from_bytes(&dawg.to_bytes()).unwrap();
+
+        assert_eq!(loaded.root, dawg.root);
+        assert_eq!(loaded.nodes, dawg.nodes);
+        assert_eq!(loaded.edges, dawg.edges);
+    }
+
+    #[test]
+    fn rejects_bad_bytes() {
+        let bytes = build(&["cat"]).to_bytes();
+
+        assert!(Dawg::from_bytes(&bytes[..bytes.len() - 4]).is_none());
+        assert!(Dawg::from_bytes(b"GWAD").is_none());
+
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert!(Dawg::from_bytes(&version).is_none());
+    }
+}
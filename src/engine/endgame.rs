@@ -0,0 +1,320 @@
+use std::time::{Duration, Instant};
+
+use super::movegen::*;
+use super::state::*;
+use super::turn::*;
+
+/// How long the expert bot and the analysis search an endgame.
+pub const ENDGAME_TIME: Duration = Duration::from_secs(3);
+
+/// Optional bounds on an endgame search, for when searching to the end of
+/// the game would take too long. Without any the search is exact.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EndgameLimits {
+    /// Stop deepening after this many moves.
+    pub depth: Option<usize>,
+    /// Only try this many of the best scoring moves at each turn, besides
+    /// passing.
+    pub width: Option<usize>,
+    /// Stop deepening once the search has run this long. The deepest search
+    /// finished by then is kept, so results depend on the machine.
+    pub time: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EndgameMove {
+    Play(Candidate),
+    Pass,
+}
+
+/// The best sequence of moves found and the spread it ends with for the
+/// player who was to move.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndgameSolution {
+    pub line: Vec<EndgameMove>,
+    pub spread: i32,
+    /// How many moves ahead the search looked.
+    pub depth: usize,
+    /// Whether every move was searched to the end of the game, so `spread`
+    /// is what the player to move can get however the others play.
+    pub complete: bool,
+}
+
+/// Searches the endgame once the bag is empty and every rack is known,
+/// deepening one move at a time until every line reaches the end of the
+/// game or one of `limits` is hit. With more than two players the others
+/// are assumed to play against the player to move.
+///
+/// Returns `None` unless the bag is empty and the player to move has a
+/// move to make, rather than a play that went out to challenge or accept.
+pub fn solve_endgame(state: &State, limits: EndgameLimits) -> Option<EndgameSolution> {
+    if !state.tile_bag.is_empty() || state.finished || state.going_out().is_some() {
+        return None;
+    }
+
+    // Only valid words are generated, so there is nothing to challenge.
+    let mut state = state.clone();
+    state.challenge_rule = ChallengeRule::Void;
+
+    let mut solver = Solver {
+        me: state.current,
+        width: limits.width.unwrap_or(usize::MAX),
+        deadline: limits.time.map(|time| Instant::now() + time),
+        principal: vec![],
+        cut_off: false,
+        narrowed: false,
+    };
+
+    let mut solution = None;
+    for depth in 1.. {
+        solver.cut_off = false;
+        let (spread, line) = match solver.search(&state, depth, 0, i32::MIN + 1, i32::MAX, true) {
+            Some(result) => result,
+            None => break,
+        };
+
+        solver.principal = line.clone();
+        solution = Some(EndgameSolution {
+            line,
+            spread,
+            depth,
+            complete: !solver.cut_off && !solver.narrowed,
+        });
+        if !solver.cut_off || limits.depth.is_some_and(|max| depth >= max) {
+            break;
+        }
+    }
+
+    solution
+}
+
+struct Solver {
+    me: usize,
+    width: usize,
+    deadline: Option<Instant>,
+    /// The best line of the previous, shallower search, tried first.
+    principal: Vec<EndgameMove>,
+    /// Whether a line was stopped by the depth before the game ended.
+    cut_off: bool,
+    /// Whether a move was left out because of the width.
+    narrowed: bool,
+}
+
+impl Solver {
+    /// Minimax with alpha-beta pruning on the spread of `self.me`, who
+    /// maximizes while everyone else minimizes. Returns `None` once out of
+    /// time, unless no search has finished yet.
+    fn search(
+        &mut self,
+        state: &State,
+        depth: usize,
+        ply: usize,
+        mut alpha: i32,
+        mut beta: i32,
+        on_principal: bool,
+    ) -> Option<(i32, Vec<EndgameMove>)> {
+        if state.finished {
+            return Some((self.evaluate(state), vec![]));
+        }
+        if depth == 0 {
+            self.cut_off = true;
+            return Some((self.evaluate(state), vec![]));
+        }
+        let finished_one = !self.principal.is_empty();
+        if finished_one
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return None;
+        }
+
+        let maximizing = state.current == self.me;
+        // Once everyone else has passed, passing again repeats the position,
+        // so it is scored as if the scoreless turns ran out.
+        let passes = state
+            .history
+            .iter()
+            .rev()
+            .take_while(|record| record.play == Play::Pass)
+            .count();
+        let repeats = passes + 1 >= state.players.len();
+
+        let mut moves = self.moves(state);
+        let mut follows = false;
+        if on_principal {
+            let first = self.principal.get(ply);
+            if let Some(i) = first.and_then(|first| moves.iter().position(|m| m == first)) {
+                let first = moves.remove(i);
+                moves.insert(0, first);
+                follows = true;
+            }
+        }
+
+        let mut best: Option<(i32, Vec<EndgameMove>)> = None;
+        for (i, next) in moves.into_iter().enumerate() {
+            if next == EndgameMove::Pass && repeats {
+                let value = self.evaluate(state);
+                if better(&best, value, maximizing) {
+                    best = Some((value, vec![next]));
+                }
+                continue;
+            }
+
+            let mut child = state.clone();
+            let played = match &next {
+                EndgameMove::Play(candidate) => {
+                    child.place_move(&candidate.tiles) && child.act(Action::Submit).is_ok()
+                }
+                EndgameMove::Pass => child.act(Action::Pass).is_ok(),
+            };
+            if !played {
+                continue;
+            }
+
+            let follows = follows && i == 0;
+            let (value, mut line) =
+                self.search(&child, depth - 1, ply + 1, alpha, beta, follows)?;
+            if better(&best, value, maximizing) {
+                line.insert(0, next);
+                best = Some((value, line));
+            }
+
+            if maximizing {
+                alpha = alpha.max(value);
+            } else {
+                beta = beta.min(value);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        Some(best.unwrap_or_else(|| (self.evaluate(state), vec![])))
+    }
+
+    /// Moves that go out first, then the best scoring moves, since they
+    /// usually prune the most, followed by a pass.
+    fn moves(&mut self, state: &State) -> Vec<EndgameMove> {
+        let mut candidates = ranked(state, usize::MAX);
+        if candidates.len() > self.width {
+            candidates.truncate(self.width);
+            self.narrowed = true;
+        }
+        let rack = state.player().tiles.len();
+        candidates.sort_by_key(|candidate| candidate.tiles.len() != rack);
+
+        candidates
+            .into_iter()
+            .map(EndgameMove::Play)
+            .chain(std::iter::once(EndgameMove::Pass))
+            .collect()
+    }
+
+    /// The spread of `self.me`, with the tiles still on each rack counted
+    /// against its owner if the game is not over.
+    fn evaluate(&self, state: &State) -> i32 {
+        if state.finished {
+            return state.spread(self.me) as i32;
+        }
+
+        let scores: Vec<i64> = state
+            .players
+            .iter()
+            .map(|player| player.score as i64 - player.rack_value() as i64)
+            .collect();
+        let best_other = scores
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != self.me)
+            .map(|(_, &score)| score)
+            .max()
+            .unwrap_or(0);
+
+        (scores[self.me] - best_other) as i32
+    }
+}
+
+/// Whether `value` improves on `best` for the player to move.
+fn better(best: &Option<(i32, Vec<EndgameMove>)>, value: i32, maximizing: bool) -> bool {
+    match best {
+        None => true,
+        Some((best, _)) if maximizing => value > *best,
+        Some((best, _)) => value < *best,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::lexicon::Lexicon;
+    use crate::engine::player::Player;
+    use crate::engine::testing::*;
+
+    #[test]
+    fn waits_for_an_empty_bag() {
+        let state = bot_game(10, 4);
+        assert_eq!(solve_endgame(&state, EndgameLimits::default()), None);
+    }
+
+    #[test]
+    fn plays_its_own_line() {
+        let mut state = play_until(11, |state| state.tile_bag.is_empty());
+        let solution = solve_endgame(
+            &state,
+            EndgameLimits {
+                depth: Some(2),
+                width: Some(6),
+                time: None,
+            },
+        )
+        .unwrap();
+
+        assert!(!solution.line.is_empty());
+        match &solution.line[0] {
+            EndgameMove::Play(candidate) => {
+                assert!(state.place_move(&candidate.tiles));
+                assert!(state.act(Action::Submit).is_ok());
+            }
+            EndgameMove::Pass => assert!(state.act(Action::Pass).is_ok()),
+        }
+    }
+
+    /// AXE scores the most, but VAV then goes out and strands the J.
+    #[test]
+    fn beats_the_greedy_move() {
+        let mut state = State::new(Some(1));
+        deal(&mut state, 0, "JAXE");
+        deal(&mut state, 1, "VV");
+        state.tile_bag.clear();
+
+        let solution = solve_endgame(&state, EndgameLimits::default()).unwrap();
+        assert!(solution.complete);
+        assert_eq!(solution.spread, 11);
+
+        let mut greedy = state.clone();
+        for word in ["axe", "vav"] {
+            let best = ranked(&greedy, 1).remove(0);
+            assert_eq!(best.words()[0], word);
+            assert_ne!(solution.line[0], EndgameMove::Play(best.clone()));
+            assert!(greedy.place_move(&best.tiles));
+            greedy.act(Action::Submit).unwrap();
+        }
+        assert!(greedy.finished);
+        assert_eq!(greedy.spread(0), -5);
+    }
+
+    #[test]
+    fn plays_against_every_other_player() {
+        let players = (0..3).map(Player::seated).collect();
+        let mut state = State::with_players(Some(1), Lexicon::sowpods(), players);
+        deal(&mut state, 0, "QIS");
+        deal(&mut state, 1, "VV");
+        deal(&mut state, 2, "UU");
+        state.tile_bag.clear();
+
+        let solution = solve_endgame(&state, EndgameLimits::default()).unwrap();
+        assert!(solution.complete);
+        assert!(matches!(solution.line[0], EndgameMove::Play(_)));
+    }
+}
@@ -0,0 +1,578 @@
+//! Games in GCG, the notation Scrabble programs use to share games.
+//!
+//! ```text
+//! #player1 p1 Player 1
+//! #player2 p2 Player 2
+//! >p1: ADEGORA 8G AGORA +10 10
+//! >p2: ?CESTRY H9 .nCESTRY +89 89
+//! >p1: EIIOUUV -IIOUU +0 10
+//! >p2: BEFLNOT - +0 89
+//! >p1: EIOOTUV 10F OUTVOTE +21 31
+//! >p1: EIOOTUV -- -21 10
+//! ```
+//!
+//! Across moves give the row first (`8G`), down moves the column (`H9`).
+//! Tiles already on the board are `.` and blanks are lowercase. A `--`
+//! line takes back a play that was challenged off.
+
+use std::fmt::{self, Write};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::board::*;
+use super::lexicon::*;
+use super::moves::*;
+use super::player::*;
+use super::state::*;
+use super::tile::*;
+use super::turn::*;
+
+const COLUMNS: &str = "ABCDEFGHIJKLMNO";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseGcgError {
+    pub line: usize,
+}
+
+impl fmt::Display for ParseGcgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid GCG on line {}.", self.line)
+    }
+}
+
+impl std::error::Error for ParseGcgError {}
+
+impl State {
+    pub fn export_gcg<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_gcg())
+    }
+
+    pub fn import_gcg<P: AsRef<Path>>(path: P) -> io::Result<State> {
+        let text = fs::read_to_string(path)?;
+        State::from_gcg(&text, Lexicon::sowpods())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the move history, with the rack adjustments once the game is
+    /// over.
+    pub fn to_gcg(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "#character-encoding UTF-8").unwrap();
+        for (i, player) in self.players.iter().enumerate() {
+            writeln!(out, "#player{} {} {}", i + 1, nick(i), player.name).unwrap();
+        }
+
+        let mut grid: Grid = [[None; 15]; 15];
+        let mut totals = vec![0i32; self.players.len()];
+
+        for (i, record) in self.history.iter().enumerate() {
+            totals[record.player] += record.points;
+            let play = match &record.play {
+                Play::Place { tiles, .. } => {
+                    for &(row, col, tile) in tiles {
+                        grid[row][col] = Some(tile);
+                    }
+                    placement_to_gcg(&grid, tiles)
+                }
+                Play::Pass | Play::FailedChallenge { lost_turn: true } => "-".to_string(),
+                Play::Exchange(returned) => format!("-{}", tiles_to_string(returned)),
+                Play::Withdrawn => {
+                    if let Some(tiles) = self.last_placement(record.player, i) {
+                        for &(row, col, _) in tiles {
+                            grid[row][col] = None;
+                        }
+                    }
+                    "--".to_string()
+                }
+                Play::ChallengeBonus => "(challenge)".to_string(),
+                // Free challenges have no notation.
+                Play::FailedChallenge { lost_turn: false } => continue,
+            };
+
+            writeln!(
+                out,
+                ">{}: {} {} {:+} {}",
+                nick(record.player),
+                tiles_to_string(&record.rack),
+                play,
+                record.points,
+                totals[record.player]
+            )
+            .unwrap();
+        }
+
+        if self.finished {
+            let mut unplayed_total = 0;
+            for (i, player) in self.players.iter().enumerate() {
+                let unplayed = player.rack_value() as i32;
+                if unplayed == 0 {
+                    continue;
+                }
+
+                unplayed_total += unplayed;
+                totals[i] -= unplayed;
+                let rack = tiles_to_string(&player.tiles);
+                writeln!(
+                    out,
+                    ">{}: {} ({}) -{} {}",
+                    nick(i),
+                    rack,
+                    rack,
+                    unplayed,
+                    totals[i]
+                )
+                .unwrap();
+            }
+
+            let out_player = self.players.iter().position(|p| p.tiles.is_empty());
+            if let (Some(i), true) = (out_player, self.tile_bag.is_empty()) {
+                let racks: Vec<Tile> = self
+                    .players
+                    .iter()
+                    .flat_map(|p| p.tiles.iter().copied())
+                    .collect();
+                totals[i] += unplayed_total;
+                writeln!(
+                    out,
+                    ">{}: ({}) +{} {}",
+                    nick(i),
+                    tiles_to_string(&racks),
+                    unplayed_total,
+                    totals[i]
+                )
+                .unwrap();
+            }
+        }
+
+        out
+    }
+
+    /// Rebuilds a game from GCG for review. Racks that are not given by
+    /// the end of game adjustments are refilled from the unseen tiles.
+    pub fn from_gcg(text: &str, words: Lexicon) -> Result<State, ParseGcgError> {
+        let (nicks, players) = seat_players(text)?;
+        let mut state = State::with_players(None, words, players);
+        let mut grid: Grid = [[None; 15]; 15];
+        let mut racks: Vec<Option<Vec<Tile>>> = vec![None; state.players.len()];
+
+        for player in state.players.iter_mut() {
+            player.tiles.clear();
+        }
+
+        for (i, line) in text.lines().enumerate() {
+            let error = ParseGcgError { line: i + 1 };
+            let line = match line.trim().strip_prefix('>') {
+                Some(line) => line,
+                None => continue,
+            };
+
+            let (nick, rest) = line.split_once(':').ok_or(error.clone())?;
+            let player = nicks
+                .iter()
+                .position(|n| n == nick.trim())
+                .ok_or(error.clone())?;
+
+            let tokens: Vec<&str> = rest.split_whitespace().collect();
+            let (play, points, total) = match tokens.as_slice() {
+                [rack, position, word, points, total] => {
+                    let rack = parse_tiles(rack).ok_or(error.clone())?;
+                    let tiles = parse_placement(&grid, position, word).ok_or(error.clone())?;
+                    for &(row, col, tile) in &tiles {
+                        grid[row][col] = Some(tile);
+                    }
+
+                    let mut leave = rack.clone();
+                    for (_, _, tile) in &tiles {
+                        if let Some(i) = leave.iter().position(|&t| t == tile.rack_tile()) {
+                            leave.remove(i);
+                        }
+                    }
+                    racks[player] = Some(leave);
+
+                    let words = word_strings(&grid, &tiles);
+                    (Some((rack, Play::Place { tiles, words })), *points, *total)
+                }
+                [rack, "--", points, total] => {
+                    let turns = state.history.len();
+                    let tiles = state.last_placement(player, turns).ok_or(error.clone())?;
+                    for &(row, col, _) in tiles {
+                        grid[row][col] = None;
+                    }
+
+                    let rack = parse_tiles(rack).ok_or(error.clone())?;
+                    racks[player] = Some(rack.clone());
+                    (Some((rack, Play::Withdrawn)), *points, *total)
+                }
+                [rack, "(challenge)", points, total] => {
+                    let rack = parse_tiles(rack).ok_or(error.clone())?;
+                    (Some((rack, Play::ChallengeBonus)), *points, *total)
+                }
+                [rack, play, points, total] if play.starts_with('-') => {
+                    let rack = parse_tiles(rack).ok_or(error.clone())?;
+                    let play = match &play[1..] {
+                        "" => Play::Pass,
+                        returned => match returned.parse::<usize>() {
+                            Ok(_) => Play::Exchange(vec![]),
+                            Err(_) => Play::Exchange(parse_tiles(returned).ok_or(error.clone())?),
+                        },
+                    };
+                    racks[player] = Some(rack.clone());
+                    (Some((rack, play)), *points, *total)
+                }
+                [rack, adjustment, points, total] if adjustment.starts_with('(') => {
+                    // Time penalties only change the total, unplayed tiles
+                    // also stay on the rack.
+                    if parse_tiles(adjustment.trim_matches(|c| c == '(' || c == ')')).is_some() {
+                        racks[player] = Some(parse_tiles(rack).ok_or(error.clone())?);
+                        state.finished = true;
+                    }
+                    (None, *points, *total)
+                }
+                [adjustment, points, total] if adjustment.starts_with('(') => {
+                    racks[player] = Some(vec![]);
+                    state.finished = true;
+                    (None, *points, *total)
+                }
+                _ => return Err(error),
+            };
+
+            let points: i32 = points.parse().map_err(|_| error.clone())?;
+            let total: i32 = total.parse().map_err(|_| error.clone())?;
+            if let Some((rack, play)) = play {
+                // Withdrawals and bonuses happen during the next player's
+                // turn.
+                if let Play::Place { .. } | Play::Pass | Play::Exchange(_) = play {
+                    state.current = (player + 1) % state.players.len();
+                }
+                state.history.push(Record {
+                    player,
+                    rack,
+                    play,
+                    points,
+                });
+            }
+            state.players[player].score = total.max(0) as u32;
+        }
+
+        state.tile_bag = Tile::iter()
+            .flat_map(|&tile| std::iter::repeat_n(tile, tile.get_quantity() as usize))
+            .collect();
+        let on_board = grid.iter().flatten().flatten().map(|tile| tile.rack_tile());
+        let on_racks = racks.iter().flatten().flatten().copied();
+        for tile in on_board.chain(on_racks) {
+            if let Some(i) = state.tile_bag.iter().position(|&t| t == tile) {
+                state.tile_bag.swap_remove(i);
+            }
+        }
+
+        state.board = Board::new();
+        for (row, line) in grid.iter().enumerate() {
+            for (col, square) in line.iter().enumerate() {
+                if let Some(tile) = square {
+                    state.board.place(row, col, tile.rack_tile());
+                    state.board.assign_blank(row, col, tile.letter());
+                }
+            }
+        }
+        state.board.commit();
+
+        for (player, rack) in state.players.iter_mut().zip(racks) {
+            player.tiles = rack.unwrap_or_default();
+            if !state.finished {
+                player.fill_tiles(&mut state.tile_bag, &mut state.rng);
+            }
+        }
+
+        state.score = state
+            .history
+            .iter()
+            .map(|record| record.points)
+            .sum::<i32>()
+            .max(0) as u32;
+        state.scoreless_turns = state
+            .history
+            .iter()
+            .rev()
+            .take_while(|record| record.points == 0)
+            .count();
+
+        Ok(state)
+    }
+}
+
+/// The nicknames and players named by the `#player` pragmas, followed by
+/// any other nicknames that make moves.
+fn seat_players(text: &str) -> Result<(Vec<String>, Vec<Player>), ParseGcgError> {
+    let mut nicks: Vec<String> = vec![];
+    let mut players = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        let error = ParseGcgError { line: i + 1 };
+        let line = line.trim();
+
+        let (nick, name) = if let Some(pragma) = line.strip_prefix("#player") {
+            let mut tokens = pragma.split_whitespace().skip(1);
+            let nick = tokens.next().ok_or(error.clone())?;
+            (nick, tokens.collect::<Vec<_>>().join(" "))
+        } else if let Some(line) = line.strip_prefix('>') {
+            let (nick, _) = line.split_once(':').ok_or(error.clone())?;
+            (nick.trim(), String::new())
+        } else {
+            continue;
+        };
+
+        if nicks.iter().any(|n| n == nick) {
+            continue;
+        }
+        if nicks.len() == MAX_PLAYERS {
+            return Err(error);
+        }
+
+        let mut player = Player::seated(players.len());
+        if !name.is_empty() {
+            player.name = name;
+        }
+        nicks.push(nick.to_string());
+        players.push(player);
+    }
+
+    while players.len() < MIN_PLAYERS {
+        players.push(Player::seated(players.len()));
+    }
+    Ok((nicks, players))
+}
+
+fn nick(player: usize) -> String {
+    format!("p{}", player + 1)
+}
+
+/// The direction of a placement on `grid`, which already holds its tiles.
+/// A single tile counts as across when it has a neighbour in its row.
+fn direction(grid: &Grid, tiles: &[(usize, usize, PlacedTile)]) -> Direction {
+    match tiles {
+        [(r1, _, _), (r2, _, _), ..] if r1 == r2 => Direction::Across,
+        [_, _, ..] => Direction::Down,
+        [(row, col, _)] => {
+            let left = *col > 0 && grid[*row][col - 1].is_some();
+            let right = *col < 14 && grid[*row][col + 1].is_some();
+            if left || right {
+                Direction::Across
+            } else {
+                Direction::Down
+            }
+        }
+        [] => Direction::Across,
+    }
+}
+
+/// The squares of the word through `(row, col)` in `direction`.
+fn word_span(grid: &Grid, row: usize, col: usize, direction: Direction) -> Vec<(usize, usize)> {
+    let step = |(r, c): (usize, usize), forward: bool| -> Option<(usize, usize)> {
+        let (r, c) = match (direction, forward) {
+            (Direction::Across, true) => (r, c + 1),
+            (Direction::Across, false) => (r, c.checked_sub(1)?),
+            (Direction::Down, true) => (r + 1, c),
+            (Direction::Down, false) => (r.checked_sub(1)?, c),
+        };
+        (r < 15 && c < 15 && grid[r][c].is_some()).then_some((r, c))
+    };
+
+    let mut start = (row, col);
+    while let Some(previous) = step(start, false) {
+        start = previous;
+    }
+
+    let mut span = vec![start];
+    while let Some(next) = step(*span.last().unwrap(), true) {
+        span.push(next);
+    }
+    span
+}
+
+/// The coordinate and main word of a placement, e.g. `8G AG.RA`.
+fn placement_to_gcg(grid: &Grid, tiles: &[(usize, usize, PlacedTile)]) -> String {
+    let (row, col, _) = match tiles.first() {
+        Some(&tile) => tile,
+        None => return "-".to_string(),
+    };
+
+    let direction = direction(grid, tiles);
+    let span = word_span(grid, row, col, direction);
+    let (start_row, start_col) = span[0];
+    let column = &COLUMNS[start_col..=start_col];
+    let position = match direction {
+        Direction::Across => format!("{}{}", start_row + 1, column),
+        Direction::Down => format!("{}{}", column, start_row + 1),
+    };
+
+    let word: String = span
+        .iter()
+        .map(
+            |&(r, c)| match tiles.iter().find(|&&(tr, tc, _)| (tr, tc) == (r, c)) {
+                Some(&(_, _, tile)) => tile.to_char(),
+                None => '.',
+            },
+        )
+        .collect();
+
+    format!("{} {}", position, word)
+}
+
+/// Reads a coordinate and word into the tiles it adds to `grid`. Tiles
+/// already there may be given as `.`, as letters, or in parentheses.
+fn parse_placement(
+    grid: &Grid,
+    position: &str,
+    word: &str,
+) -> Option<Vec<(usize, usize, PlacedTile)>> {
+    let (direction, column, row) = if position.starts_with(|c: char| c.is_ascii_digit()) {
+        let split = position.find(|c: char| !c.is_ascii_digit())?;
+        (Direction::Across, &position[split..], &position[..split])
+    } else {
+        let split = position
+            .char_indices()
+            .nth(1)
+            .map_or(position.len(), |(i, _)| i);
+        (Direction::Down, &position[..split], &position[split..])
+    };
+
+    let mut letters = column.chars();
+    let column = match (letters.next(), letters.next()) {
+        (Some(letter), None) => COLUMNS.find(letter.to_ascii_uppercase())?,
+        _ => return None,
+    };
+    let row = row.parse::<usize>().ok()?.checked_sub(1)?;
+
+    let mut tiles = vec![];
+    let (mut r, mut c) = (row, column);
+    for ch in word.chars().filter(|&ch| ch != '(' && ch != ')') {
+        if r >= 15 || c >= 15 {
+            return None;
+        }
+
+        match (ch, grid[r][c]) {
+            ('.', Some(_)) => {}
+            (ch, Some(tile)) if ch.eq_ignore_ascii_case(&tile.to_char()) => {}
+            (ch, None) if ch.is_ascii_uppercase() => {
+                tiles.push((r, c, PlacedTile::Letter(Tile::from(ch))))
+            }
+            (ch, None) if ch.is_ascii_lowercase() => {
+                tiles.push((r, c, PlacedTile::Blank(Tile::from(ch))))
+            }
+            _ => return None,
+        }
+
+        match direction {
+            Direction::Across => c += 1,
+            Direction::Down => r += 1,
+        }
+    }
+
+    if tiles.is_empty() {
+        return None;
+    }
+    Some(tiles)
+}
+
+/// The words a placement formed on `grid`, main word first, in lowercase.
+fn word_strings(grid: &Grid, tiles: &[(usize, usize, PlacedTile)]) -> Vec<String> {
+    let placed: Vec<(usize, usize)> = tiles.iter().map(|&(row, col, _)| (row, col)).collect();
+    formed_words(grid, &placed, direction(grid, tiles))
+        .into_iter()
+        .map(|word| word.text)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::testing::*;
+
+    fn reimport(state: &State) -> State {
+        State::from_gcg(&state.to_gcg(), state.words.clone())
+            .ok()
+            .unwrap()
+    }
+
+    #[test]
+    fn round_trips() {
+        for (seed, turns) in [(1, 10), (2, 200), (3, 200)] {
+            let state = bot_game(seed, turns);
+            let loaded = reimport(&state);
+
+            assert_eq!(loaded.to_gcg(), state.to_gcg());
+            assert_eq!(loaded.board.squares(), state.board.squares());
+            assert_eq!(loaded.finished, state.finished);
+            if !state.finished {
+                assert_eq!(loaded.current, state.current);
+            }
+            for (a, b) in loaded.players.iter().zip(&state.players) {
+                assert_eq!(a.score, b.score);
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_names_and_withdrawn_plays() {
+        let mut state = State::new(Some(8));
+        state.challenge_rule = ChallengeRule::Single;
+        state.players[0].name = "Ada Lovelace".to_string();
+        deal(&mut state, 0, "QZEEEEE");
+        place(&mut state, 7, 7, "qz");
+        state.act(Action::Submit).unwrap();
+        state.act(Action::Challenge).unwrap();
+
+        let text = state.to_gcg();
+        assert!(text.contains("#player1 p1 Ada Lovelace"));
+        assert!(text.contains(">p1: EEEEEQZ 8H QZ +40 40"));
+        assert!(text.contains(">p1: EEEEEQZ -- -40 0"));
+
+        let loaded = reimport(&state);
+        assert_eq!(loaded.players[0].name, "Ada Lovelace");
+        assert_eq!(
+            loaded.history.last().map(|r| &r.play),
+            Some(&Play::Withdrawn)
+        );
+        assert!(loaded.board.squares().iter().flatten().all(Option::is_none));
+        assert_eq!(loaded.to_gcg(), text);
+    }
+
+    #[test]
+    fn reads_other_programs_games() {
+        let text = "#player1 ann Ann\n\
+                    #player2 bob Bob\n\
+                    >ann: ACEHLMT 8D MATCH +30 30\n\
+                    >bob: BEIORTU E5 TUB. +12 12\n";
+        let state = State::from_gcg(text, Lexicon::sowpods()).ok().unwrap();
+
+        assert_eq!(state.players[0].name, "Ann");
+        assert_eq!(state.players[1].score, 12);
+        assert_eq!(state.current, 0);
+        assert_eq!(state.history.len(), 2);
+        assert_eq!(state.board.get(7, 3), Some(PlacedTile::Letter(Tile::M)));
+        assert_eq!(state.board.get(4, 4), Some(PlacedTile::Letter(Tile::T)));
+    }
+
+    #[test]
+    fn reports_the_bad_line() {
+        let error = |text: &str| State::from_gcg(text, Lexicon::sowpods()).err();
+
+        assert_eq!(
+            error("#player1 a A\n>a: ABC 8H CAB +14\n"),
+            Some(ParseGcgError { line: 2 })
+        );
+        assert_eq!(
+            error(">a: ABC 8Z CAB +14 14\n"),
+            Some(ParseGcgError { line: 1 })
+        );
+        for position in ["é8", "8é", "8GH", "GH8"] {
+            let text = format!(">a: ABC {} CAB +10 10\n", position);
+            assert_eq!(error(&text), Some(ParseGcgError { line: 1 }));
+        }
+        assert_eq!(
+            error(">a: ABC 8H CAB +14 14\n>b: DEF 9Q FED +9 9\n"),
+            Some(ParseGcgError { line: 2 })
+        );
+    }
+}
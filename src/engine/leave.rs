@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::tile::*;
+
+const DEFAULT_LEAVES: &str = include_str!("../../assets/leaves.txt");
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseLeaveError {
+    pub line: usize,
+}
+
+impl fmt::Display for ParseLeaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid leave on line {}.", self.line)
+    }
+}
+
+impl std::error::Error for ParseLeaveError {}
+
+/// Values for the tiles kept on the rack after a move, keyed by the sorted
+/// tiles.
+#[derive(Clone, Debug, Default)]
+pub struct LeaveTable {
+    values: HashMap<Vec<Tile>, f32>,
+}
+
+impl LeaveTable {
+    /// The table shipped in `assets/leaves.txt`.
+    pub fn standard() -> LeaveTable {
+        LeaveTable::parse(DEFAULT_LEAVES).expect("assets/leaves.txt is invalid")
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<LeaveTable> {
+        let text = fs::read_to_string(path)?;
+        LeaveTable::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Reads one `LEAVE VALUE` pair per line, with `?` for a blank. Blank
+    /// lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> Result<LeaveTable, ParseLeaveError> {
+        let mut values = HashMap::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = ParseLeaveError { line: i + 1 };
+            let mut parts = line.split_whitespace();
+            let (leave, value) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(leave), Some(value), None) => (leave, value),
+                _ => return Err(error),
+            };
+
+            if !leave.chars().all(|c| c.is_ascii_alphabetic() || c == '?') {
+                return Err(error);
+            }
+            let value: f32 = value.parse().map_err(|_| error.clone())?;
+
+            let mut tiles: Vec<Tile> = leave.chars().map(Tile::from).collect();
+            tiles.sort_by_key(Tile::index);
+            values.insert(tiles, value);
+        }
+
+        Ok(LeaveTable { values })
+    }
+
+    /// The value of keeping `leave`. Leaves missing from the table are
+    /// valued as the sum of their tiles.
+    pub fn value(&self, leave: &[Tile]) -> f32 {
+        let mut tiles = leave.to_vec();
+        tiles.sort_by_key(Tile::index);
+
+        match self.values.get(&tiles) {
+            Some(&value) => value,
+            None => tiles
+                .iter()
+                .map(|&tile| self.values.get(&vec![tile]).copied().unwrap_or(0.0))
+                .sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_and_sums_leaves() {
+        let table = LeaveTable::parse("# test\nS 8\n? 25.5\n\nQ -7\nQU -3\n").unwrap();
+
+        assert_eq!(table.value(&[Tile::U, Tile::Q]), -3.0);
+        assert_eq!(table.value(&[Tile::S, Tile::Blank]), 33.5);
+        assert_eq!(table.value(&[Tile::E]), 0.0);
+    }
+
+    #[test]
+    fn reports_the_bad_line() {
+        assert_eq!(
+            LeaveTable::parse("S 8\nQU\n").err(),
+            Some(ParseLeaveError { line: 2 })
+        );
+        assert_eq!(
+            LeaveTable::parse("S 8\n\nS1 2\n").err(),
+            Some(ParseLeaveError { line: 3 })
+        );
+        assert!(LeaveTable::parse(DEFAULT_LEAVES).is_ok());
+    }
+}
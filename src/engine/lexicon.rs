@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use super::dawg::Dawg;
+use super::tile::*;
+
+/// `assets/sowpods.txt` compiled into a `Dawg` by `build.rs`.
+static SOWPODS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/sowpods.dawg"));
+
+/// Every letter from A to Z as a bit set.
+pub const ALL_LETTERS: u32 = (1 << 26) - 1;
+
+/// The word list, stored as a word graph that can be walked letter by
+/// letter. Cloning it is cheap.
+#[derive(Clone)]
+pub struct Lexicon {
+    dawg: Arc<Dawg>,
+}
+
+impl Lexicon {
+    /// The embedded SOWPODS word list.
+    pub fn sowpods() -> Lexicon {
+        Lexicon::from_bytes(SOWPODS).expect("sowpods.dawg is corrupt")
+    }
+
+    pub fn from_words<I, S>(words: I) -> Lexicon
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let words: Vec<String> = words
+            .into_iter()
+            .map(|word| word.as_ref().to_ascii_lowercase())
+            .collect();
+        let words: Vec<&[u8]> = words.iter().map(|word| word.as_bytes()).collect();
+
+        Lexicon {
+            dawg: Arc::new(Dawg::build(&words)),
+        }
+    }
+
+    /// Reads a graph written by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Lexicon> {
+        Some(Lexicon {
+            dawg: Arc::new(Dawg::from_bytes(bytes)?),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.dawg.to_bytes()
+    }
+
+    pub fn root(&self) -> u32 {
+        self.dawg.root()
+    }
+
+    pub fn is_terminal(&self, node: u32) -> bool {
+        self.dawg.is_terminal(node)
+    }
+
+    pub fn children(&self, node: u32) -> impl Iterator<Item = (Tile, u32)> + '_ {
+        self.dawg
+            .children(node)
+            .map(|(letter, target)| (Tile::from_index(letter as usize), target))
+    }
+
+    pub fn child(&self, node: u32, letter: Tile) -> Option<u32> {
+        if letter == Tile::Blank {
+            return None;
+        }
+
+        self.dawg.child(node, letter.index() as u8)
+    }
+
+    /// Follows `letters` from `node`.
+    pub fn walk<I: IntoIterator<Item = Tile>>(&self, node: u32, letters: I) -> Option<u32> {
+        letters
+            .into_iter()
+            .try_fold(node, |node, letter| self.child(node, letter))
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.walk(self.root(), word.chars().map(Tile::from))
+            .is_some_and(|node| self.is_terminal(node))
+    }
+
+    /// Whether some word starts with `prefix`.
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        self.walk(self.root(), prefix.chars().map(Tile::from))
+            .is_some()
+    }
+
+    /// The letters that can go between `before` and `after` to make a word,
+    /// as a bit set indexed by `Tile::index`.
+    pub fn cross_set(&self, before: &[Tile], after: &[Tile]) -> u32 {
+        let node = match self.walk(self.root(), before.iter().copied()) {
+            Some(node) => node,
+            None => return 0,
+        };
+
+        self.children(node)
+            .filter(|&(_, next)| {
+                self.walk(next, after.iter().copied())
+                    .is_some_and(|end| self.is_terminal(end))
+            })
+            .fold(0, |set, (letter, _)| set | 1 << letter.index())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_words_and_prefixes() {
+        let lexicon = Lexicon::from_words(["Cat", "cats", "dog"]);
+
+        assert!(lexicon.contains("cat"));
+        assert!(lexicon.contains("CATS"));
+        assert!(!lexicon.contains("ca"));
+        assert!(lexicon.has_prefix("ca"));
+        assert!(!lexicon.has_prefix("cx"));
+    }
+
+    #[test]
+    fn cross_sets() {
+        let lexicon = Lexicon::from_words(["cat", "cot", "cut", "at", "it"]);
+        let set = |letters: &[Tile]| letters.iter().fold(0, |set, t| set | 1 << t.index());
+
+        assert_eq!(
+            lexicon.cross_set(&[Tile::C], &[Tile::T]),
+            set(&[Tile::A, Tile::O, Tile::U])
+        );
+        assert_eq!(lexicon.cross_set(&[], &[Tile::T]), set(&[Tile::A, Tile::I]));
+        assert_eq!(lexicon.cross_set(&[Tile::X], &[]), 0);
+    }
+
+    #[test]
+    fn sowpods_loads() {
+        let lexicon = Lexicon::sowpods();
+        assert!(lexicon.contains("quixotic"));
+        assert!(lexicon.contains("za"));
+        assert!(!lexicon.contains("qz"));
+    }
+}
@@ -0,0 +1,49 @@
+mod board;
+pub use board::*;
+
+mod bot;
+pub use bot::*;
+
+mod dawg;
+
+mod endgame;
+pub use endgame::*;
+
+mod gcg;
+pub use gcg::*;
+
+mod leave;
+pub use leave::*;
+
+mod lexicon;
+pub use lexicon::*;
+
+mod movegen;
+pub use movegen::*;
+
+mod moves;
+pub use moves::*;
+
+mod player;
+pub use player::*;
+
+mod save;
+pub use save::*;
+
+mod score;
+pub use score::*;
+
+mod sim;
+pub use sim::*;
+
+mod state;
+pub use state::*;
+
+mod tile;
+pub use tile::*;
+
+mod turn;
+pub use turn::*;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,380 @@
+use std::collections::HashSet;
+
+use super::board::*;
+use super::lexicon::*;
+use super::moves::*;
+use super::score::*;
+use super::state::*;
+use super::tile::*;
+
+/// A legal move and what it scores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    pub tiles: Vec<(usize, usize, PlacedTile)>,
+    pub direction: Direction,
+    pub score: MoveScore,
+}
+
+impl Candidate {
+    /// What is left of `rack` after this move.
+    pub fn leave(&self, rack: &[Tile]) -> Vec<Tile> {
+        let mut leave = rack.to_vec();
+
+        for (_, _, tile) in &self.tiles {
+            if let Some(i) = leave.iter().position(|&t| t == tile.rack_tile()) {
+                leave.remove(i);
+            }
+        }
+
+        leave
+    }
+
+    /// The words formed, main word first.
+    pub fn words(&self) -> Vec<&str> {
+        self.score.words.iter().map(|w| w.word.as_str()).collect()
+    }
+}
+
+/// The `n` best scoring moves for the player to move, best first.
+pub fn ranked(state: &State, n: usize) -> Vec<Candidate> {
+    let mut candidates = generate(&state.board, &state.player().tiles, &state.words);
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.score.total));
+    candidates.truncate(n);
+    candidates
+}
+
+/// Finds every legal placement of `rack` on `board`, which must have no
+/// tiles placed this turn.
+pub fn generate(board: &Board, rack: &[Tile], lexicon: &Lexicon) -> Vec<Candidate> {
+    let mut counts = [0u8; 27];
+    for tile in rack {
+        counts[tile.index()] += 1;
+    }
+
+    let squares = board.squares();
+    let mut transposed = [[None; 15]; 15];
+    for (row, line) in squares.iter().enumerate() {
+        for (col, square) in line.iter().enumerate() {
+            transposed[col][row] = *square;
+        }
+    }
+
+    let mut raw = vec![];
+    for (grid, direction) in [(squares, Direction::Across), (&transposed, Direction::Down)] {
+        for row in 0..15 {
+            let mut generator = LineGenerator {
+                lexicon,
+                line: grid[row],
+                cross: cross_checks(grid, row, lexicon),
+                anchors: anchors(grid, row),
+                counts,
+                found: vec![],
+            };
+            generator.run();
+
+            for tiles in generator.found {
+                let tiles: Vec<(usize, usize, PlacedTile)> = tiles
+                    .into_iter()
+                    .map(|(col, tile)| match direction {
+                        Direction::Across => (row, col, tile),
+                        Direction::Down => (col, row, tile),
+                    })
+                    .collect();
+                raw.push(tiles);
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    raw.into_iter()
+        .filter_map(|mut tiles| {
+            tiles.sort_by_key(|&(row, col, _)| (row, col));
+            if !seen.insert(tiles.clone()) {
+                return None;
+            }
+            evaluate(board, tiles, lexicon)
+        })
+        .collect()
+}
+
+/// Scores `tiles` on a copy of `board` through the same rules a submitted
+/// move goes through.
+pub fn evaluate(
+    board: &Board,
+    tiles: Vec<(usize, usize, PlacedTile)>,
+    lexicon: &Lexicon,
+) -> Option<Candidate> {
+    let mut board = board.clone();
+    for &(row, col, tile) in &tiles {
+        if !board.place(row, col, tile.rack_tile()) {
+            return None;
+        }
+        board.assign_blank(row, col, tile.letter());
+    }
+
+    let direction = board.validate_move().ok()?;
+    let words = board.formed_words(direction);
+    if words.is_empty() || !words.iter().all(|word| lexicon.contains(&word.text)) {
+        return None;
+    }
+
+    let score = board.score_move(&words);
+    Some(Candidate {
+        tiles,
+        direction,
+        score,
+    })
+}
+
+/// For each square of `row`, the letters that form a valid word with the
+/// tiles above and below it.
+fn cross_checks(grid: &Grid, row: usize, lexicon: &Lexicon) -> [u32; 15] {
+    let mut cross = [ALL_LETTERS; 15];
+
+    for (col, check) in cross.iter_mut().enumerate() {
+        if grid[row][col].is_some() {
+            continue;
+        }
+
+        let mut top = row;
+        while top > 0 && grid[top - 1][col].is_some() {
+            top -= 1;
+        }
+        let mut bottom = row;
+        while bottom < 14 && grid[bottom + 1][col].is_some() {
+            bottom += 1;
+        }
+        if top == row && bottom == row {
+            continue;
+        }
+
+        let above: Vec<Tile> = (top..row)
+            .filter_map(|r| grid[r][col])
+            .map(|t| t.letter())
+            .collect();
+        let below: Vec<Tile> = (row + 1..=bottom)
+            .filter_map(|r| grid[r][col])
+            .map(|t| t.letter())
+            .collect();
+
+        *check = lexicon.cross_set(&above, &below);
+    }
+
+    cross
+}
+
+/// Empty squares of `row` next to a tile. On an empty board only the
+/// center is an anchor.
+fn anchors(grid: &Grid, row: usize) -> [bool; 15] {
+    let mut anchors = [false; 15];
+    let empty = grid.iter().all(|line| line.iter().all(Option::is_none));
+
+    for (col, anchor) in anchors.iter_mut().enumerate() {
+        if grid[row][col].is_some() {
+            continue;
+        }
+
+        *anchor = if empty {
+            (row, col) == CENTER
+        } else {
+            (row > 0 && grid[row - 1][col].is_some())
+                || (row < 14 && grid[row + 1][col].is_some())
+                || (col > 0 && grid[row][col - 1].is_some())
+                || (col < 14 && grid[row][col + 1].is_some())
+        };
+    }
+
+    anchors
+}
+
+struct LineGenerator<'a> {
+    lexicon: &'a Lexicon,
+    line: [Option<PlacedTile>; 15],
+    cross: [u32; 15],
+    anchors: [bool; 15],
+    counts: [u8; 27],
+    found: Vec<Vec<(usize, PlacedTile)>>,
+}
+
+impl LineGenerator<'_> {
+    fn run(&mut self) {
+        for anchor in 0..15 {
+            if !self.anchors[anchor] {
+                continue;
+            }
+
+            if anchor > 0 && self.line[anchor - 1].is_some() {
+                let mut start = anchor;
+                while start > 0 && self.line[start - 1].is_some() {
+                    start -= 1;
+                }
+
+                let prefix = (start..anchor)
+                    .filter_map(|c| self.line[c])
+                    .map(|t| t.letter());
+                if let Some(node) = self.lexicon.walk(self.lexicon.root(), prefix) {
+                    self.extend_right(node, anchor, anchor, &mut vec![]);
+                }
+            } else {
+                let mut limit = 0;
+                while limit < anchor
+                    && self.line[anchor - limit - 1].is_none()
+                    && !self.anchors[anchor - limit - 1]
+                {
+                    limit += 1;
+                }
+
+                self.left_part(self.lexicon.root(), anchor, limit, &mut vec![]);
+            }
+        }
+    }
+
+    /// Builds every prefix of up to `limit` rack tiles ending just left of
+    /// `anchor`, then extends each one rightwards.
+    fn left_part(&mut self, node: u32, anchor: usize, limit: usize, left: &mut Vec<PlacedTile>) {
+        let start = anchor - left.len();
+        let mut placed: Vec<(usize, PlacedTile)> = left
+            .iter()
+            .enumerate()
+            .map(|(i, &tile)| (start + i, tile))
+            .collect();
+        self.extend_right(node, anchor, anchor, &mut placed);
+
+        if limit == 0 {
+            return;
+        }
+
+        let children: Vec<(Tile, u32)> = self.lexicon.children(node).collect();
+        for (letter, next) in children {
+            for tile in [PlacedTile::Letter(letter), PlacedTile::Blank(letter)] {
+                if !self.take(tile) {
+                    continue;
+                }
+
+                left.push(tile);
+                self.left_part(next, anchor, limit - 1, left);
+                left.pop();
+                self.give(tile);
+            }
+        }
+    }
+
+    fn extend_right(
+        &mut self,
+        node: u32,
+        col: usize,
+        anchor: usize,
+        placed: &mut Vec<(usize, PlacedTile)>,
+    ) {
+        if col >= 15 || self.line[col].is_none() {
+            if col > anchor && self.lexicon.is_terminal(node) {
+                self.found.push(placed.clone());
+            }
+            if col >= 15 {
+                return;
+            }
+
+            let children: Vec<(Tile, u32)> = self.lexicon.children(node).collect();
+            for (letter, next) in children {
+                if self.cross[col] & (1 << letter.index()) == 0 {
+                    continue;
+                }
+
+                for tile in [PlacedTile::Letter(letter), PlacedTile::Blank(letter)] {
+                    if !self.take(tile) {
+                        continue;
+                    }
+
+                    placed.push((col, tile));
+                    self.extend_right(next, col + 1, anchor, placed);
+                    placed.pop();
+                    self.give(tile);
+                }
+            }
+        } else if let Some(tile) = self.line[col] {
+            if let Some(next) = self.lexicon.child(node, tile.letter()) {
+                self.extend_right(next, col + 1, anchor, placed);
+            }
+        }
+    }
+
+    /// Takes `tile` off the rack if it is there. It must be handed back
+    /// with `give`.
+    fn take(&mut self, tile: PlacedTile) -> bool {
+        let count = &mut self.counts[tile.rack_tile().index()];
+        if *count == 0 {
+            return false;
+        }
+
+        *count -= 1;
+        true
+    }
+
+    fn give(&mut self, tile: PlacedTile) {
+        self.counts[tile.rack_tile().index()] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rack(letters: &str) -> Vec<Tile> {
+        letters
+            .chars()
+            .map(|c| if c == '?' { Tile::Blank } else { Tile::from(c) })
+            .collect()
+    }
+
+    fn word(candidate: &Candidate) -> &str {
+        candidate.words()[0]
+    }
+
+    #[test]
+    fn opening_moves_cover_center() {
+        let lexicon = Lexicon::from_words(["cat", "act", "at", "ta"]);
+        let candidates = generate(&Board::new(), &rack("cat"), &lexicon);
+
+        assert!(candidates
+            .iter()
+            .all(|c| c.tiles.iter().any(|&(row, col, _)| (row, col) == CENTER)));
+        for direction in [Direction::Across, Direction::Down] {
+            assert!(candidates
+                .iter()
+                .any(|c| word(c) == "cat" && c.direction == direction));
+        }
+    }
+
+    #[test]
+    fn respects_cross_words() {
+        let lexicon = Lexicon::from_words(["cat", "cats", "at", "as", "ta"]);
+        let mut board = Board::new();
+        for (i, c) in "cat".chars().enumerate() {
+            board.place(7, 6 + i, Tile::from(c));
+        }
+        board.commit();
+
+        let candidates = generate(&board, &rack("sa"), &lexicon);
+        assert!(candidates.iter().any(|c| word(c) == "cats"));
+        for candidate in &candidates {
+            assert!(candidate.words().iter().all(|w| lexicon.contains(w)));
+            assert_eq!(
+                evaluate(&board, candidate.tiles.clone(), &lexicon).as_ref(),
+                Some(candidate)
+            );
+        }
+    }
+
+    #[test]
+    fn blanks_play_any_letter() {
+        let lexicon = Lexicon::from_words(["cat"]);
+        let candidates = generate(&Board::new(), &rack("ca?"), &lexicon);
+        let cat = candidates.iter().find(|c| word(c) == "cat").unwrap();
+
+        assert!(cat
+            .tiles
+            .iter()
+            .any(|&(_, _, tile)| tile == PlacedTile::Blank(Tile::T)));
+        assert_eq!(cat.leave(&rack("ca?")), vec![]);
+    }
+}
@@ -0,0 +1,343 @@
+use super::board::Grid;
+use super::lexicon::Lexicon;
+use super::tile::*;
+use std::fmt;
+
+pub const CENTER: (usize, usize) = (7, 7);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    NoTiles,
+    NotInLine,
+    Gap,
+    NotConnected,
+    MissesCenter,
+    TooShort,
+    UnassignedBlank,
+    InvalidWords(Vec<String>),
+    NothingToExchange,
+    BagTooSmall,
+    NothingToChallenge,
+    ChallengeOrAccept,
+    GameOver,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Word {
+    pub squares: Vec<(usize, usize)>,
+    pub text: String,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use MoveError::*;
+
+        match self {
+            NoTiles => write!(f, "Place at least one tile first."),
+            NotInLine => write!(f, "Tiles must be in a single row or column."),
+            Gap => write!(f, "Tiles must not leave any gaps."),
+            NotConnected => write!(f, "Tiles must touch a tile already on the board."),
+            MissesCenter => write!(f, "The first move must cover the center square."),
+            TooShort => write!(f, "The first move must be at least two letters long."),
+            UnassignedBlank => write!(f, "Choose a letter for the blank first."),
+            InvalidWords(words) => write!(f, "Not in the dictionary: {}", words.join(", ")),
+            NothingToExchange => write!(f, "Select at least one tile to exchange."),
+            BagTooSmall => write!(f, "Exchanging needs at least 7 tiles in the bag."),
+            NothingToChallenge => write!(f, "There is no play to challenge."),
+            ChallengeOrAccept => write!(f, "Challenge the last play or accept it."),
+            GameOver => write!(f, "The game is over."),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Checks the tiles placed this turn against the placement rules.
+///
+/// `board` already holds the placed tiles; `placed` lists their squares.
+/// Returns the direction of the move's main word.
+pub fn validate(board: &Grid, placed: &[(usize, usize)]) -> Result<Direction, MoveError> {
+    if placed.is_empty() {
+        return Err(MoveError::NoTiles);
+    }
+    if placed
+        .iter()
+        .any(|&(r, c)| board[r][c] == Some(PlacedTile::Blank(Tile::Blank)))
+    {
+        return Err(MoveError::UnassignedBlank);
+    }
+
+    let (row, col) = placed[0];
+    let direction = if placed.iter().all(|&(r, _)| r == row) && placed.len() > 1 {
+        Direction::Across
+    } else if placed.iter().all(|&(_, c)| c == col) && placed.len() > 1 {
+        Direction::Down
+    } else if placed.len() > 1 {
+        return Err(MoveError::NotInLine);
+    } else if (col > 0 && board[row][col - 1].is_some())
+        || (col < 14 && board[row][col + 1].is_some())
+    {
+        Direction::Across
+    } else {
+        Direction::Down
+    };
+
+    let (start, end) = match direction {
+        Direction::Across => (
+            placed.iter().map(|&(_, c)| c).min().unwrap(),
+            placed.iter().map(|&(_, c)| c).max().unwrap(),
+        ),
+        Direction::Down => (
+            placed.iter().map(|&(r, _)| r).min().unwrap(),
+            placed.iter().map(|&(r, _)| r).max().unwrap(),
+        ),
+    };
+
+    let gap = (start..=end).any(|i| match direction {
+        Direction::Across => board[row][i].is_none(),
+        Direction::Down => board[i][col].is_none(),
+    });
+
+    if gap {
+        return Err(MoveError::Gap);
+    }
+
+    let first_move = board.iter().enumerate().all(|(r, line)| {
+        line.iter()
+            .enumerate()
+            .all(|(c, square)| square.is_none() || placed.contains(&(r, c)))
+    });
+
+    if first_move {
+        if !placed.contains(&CENTER) {
+            return Err(MoveError::MissesCenter);
+        }
+
+        if placed.len() < 2 {
+            return Err(MoveError::TooShort);
+        }
+
+        return Ok(direction);
+    }
+
+    let touches = placed.iter().any(|&(r, c)| {
+        neighbours(r, c)
+            .iter()
+            .any(|&(nr, nc)| board[nr][nc].is_some() && !placed.contains(&(nr, nc)))
+    });
+
+    if !touches {
+        return Err(MoveError::NotConnected);
+    }
+
+    Ok(direction)
+}
+
+/// Finds the main word and every cross-word formed by the placed tiles.
+pub fn formed_words(board: &Grid, placed: &[(usize, usize)], direction: Direction) -> Vec<Word> {
+    let mut words = vec![];
+
+    if let Some(&(row, col)) = placed.first() {
+        words.extend(word_at(board, row, col, direction));
+    }
+
+    let cross = match direction {
+        Direction::Across => Direction::Down,
+        Direction::Down => Direction::Across,
+    };
+
+    for &(row, col) in placed {
+        words.extend(word_at(board, row, col, cross));
+    }
+
+    words
+}
+
+/// Returns the words from `words` that are missing from the lexicon.
+pub fn check_words(words: &[Word], lexicon: &Lexicon) -> Result<(), MoveError> {
+    let invalid: Vec<String> = words
+        .iter()
+        .filter(|word| !lexicon.contains(&word.text))
+        .map(|word| word.text.to_uppercase())
+        .collect();
+
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(MoveError::InvalidWords(invalid))
+    }
+}
+
+fn word_at(board: &Grid, row: usize, col: usize, direction: Direction) -> Option<Word> {
+    let (dr, dc) = match direction {
+        Direction::Across => (0, 1),
+        Direction::Down => (1, 0),
+    };
+
+    let (mut r, mut c) = (row, col);
+    while r >= dr && c >= dc && board[r - dr][c - dc].is_some() {
+        r -= dr;
+        c -= dc;
+    }
+
+    let mut squares = vec![];
+    let mut text = String::new();
+    while r < 15 && c < 15 {
+        match board[r][c] {
+            Some(tile) => {
+                let letter: &str = (&tile.letter()).into();
+                text.push_str(&letter.to_lowercase());
+                squares.push((r, c));
+            }
+            None => break,
+        }
+        r += dr;
+        c += dc;
+    }
+
+    if squares.len() < 2 {
+        return None;
+    }
+
+    Some(Word { squares, text })
+}
+
+fn neighbours(row: usize, col: usize) -> Vec<(usize, usize)> {
+    let mut neighbours = vec![];
+
+    if row > 0 {
+        neighbours.push((row - 1, col));
+    }
+    if row < 14 {
+        neighbours.push((row + 1, col));
+    }
+    if col > 0 {
+        neighbours.push((row, col - 1));
+    }
+    if col < 14 {
+        neighbours.push((row, col + 1));
+    }
+
+    neighbours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::testing::write;
+
+    fn empty() -> Grid {
+        [[None; 15]; 15]
+    }
+
+    #[test]
+    fn needs_tiles() {
+        assert_eq!(validate(&empty(), &[]), Err(MoveError::NoTiles));
+    }
+
+    #[test]
+    fn first_move_covers_center() {
+        let mut grid = empty();
+        let placed = write(&mut grid, 3, 3, Direction::Across, "cat");
+        assert_eq!(validate(&grid, &placed), Err(MoveError::MissesCenter));
+
+        let mut grid = empty();
+        let placed = write(&mut grid, 7, 6, Direction::Across, "cat");
+        assert_eq!(validate(&grid, &placed), Ok(Direction::Across));
+
+        let mut grid = empty();
+        let placed = write(&mut grid, 5, 7, Direction::Down, "cat");
+        assert_eq!(validate(&grid, &placed), Ok(Direction::Down));
+    }
+
+    #[test]
+    fn first_move_has_two_letters() {
+        let mut grid = empty();
+        let placed = write(&mut grid, 7, 7, Direction::Across, "a");
+        assert_eq!(validate(&grid, &placed), Err(MoveError::TooShort));
+    }
+
+    #[test]
+    fn blanks_need_a_letter() {
+        let mut grid = empty();
+        let placed = write(&mut grid, 7, 7, Direction::Across, "at");
+        grid[7][8] = Some(PlacedTile::Blank(Tile::Blank));
+        assert_eq!(validate(&grid, &placed), Err(MoveError::UnassignedBlank));
+
+        grid[7][8] = Some(PlacedTile::Blank(Tile::T));
+        assert_eq!(validate(&grid, &placed), Ok(Direction::Across));
+    }
+
+    #[test]
+    fn tiles_in_one_line() {
+        let mut grid = empty();
+        let mut placed = write(&mut grid, 7, 7, Direction::Across, "at");
+        placed.extend(write(&mut grid, 8, 9, Direction::Across, "s"));
+        assert_eq!(validate(&grid, &placed), Err(MoveError::NotInLine));
+    }
+
+    #[test]
+    fn no_gaps() {
+        let mut grid = empty();
+        let mut placed = write(&mut grid, 7, 6, Direction::Across, "c");
+        placed.extend(write(&mut grid, 7, 8, Direction::Across, "t"));
+        assert_eq!(validate(&grid, &placed), Err(MoveError::Gap));
+
+        // A tile already on the board fills the gap.
+        write(&mut grid, 7, 7, Direction::Across, "a");
+        assert_eq!(validate(&grid, &placed), Ok(Direction::Across));
+    }
+
+    #[test]
+    fn later_moves_connect() {
+        let mut grid = empty();
+        write(&mut grid, 7, 6, Direction::Across, "cat");
+
+        let mut apart = grid;
+        let placed = write(&mut apart, 2, 2, Direction::Across, "dog");
+        assert_eq!(validate(&apart, &placed), Err(MoveError::NotConnected));
+
+        let mut hooked = grid;
+        let placed = write(&mut hooked, 7, 9, Direction::Across, "s");
+        assert_eq!(validate(&hooked, &placed), Ok(Direction::Across));
+
+        let mut below = grid;
+        let placed = write(&mut below, 8, 6, Direction::Across, "h");
+        assert_eq!(validate(&below, &placed), Ok(Direction::Down));
+    }
+
+    #[test]
+    fn finds_main_and_cross_words() {
+        let mut grid = empty();
+        write(&mut grid, 7, 6, Direction::Across, "cat");
+        let placed = write(&mut grid, 8, 6, Direction::Across, "ha");
+
+        let words = formed_words(&grid, &placed, Direction::Across);
+        let texts: Vec<&str> = words.iter().map(|word| word.text.as_str()).collect();
+        assert_eq!(texts, ["ha", "ch", "aa"]);
+        assert_eq!(words[1].squares, [(7, 6), (8, 6)]);
+    }
+
+    #[test]
+    fn reports_invalid_words() {
+        let lexicon = Lexicon::from_words(["cat"]);
+        let mut grid = empty();
+        let placed = write(&mut grid, 7, 6, Direction::Across, "cat");
+        let words = formed_words(&grid, &placed, Direction::Across);
+        assert_eq!(check_words(&words, &lexicon), Ok(()));
+
+        let mut grid = empty();
+        let placed = write(&mut grid, 7, 6, Direction::Across, "cxt");
+        let words = formed_words(&grid, &placed, Direction::Across);
+        assert_eq!(
+            check_words(&words, &lexicon),
+            Err(MoveError::InvalidWords(vec!["CXT".to_string()]))
+        );
+    }
+}
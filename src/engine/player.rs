@@ -0,0 +1,104 @@
+use super::bot::Level;
+use super::tile::*;
+use ::rand::Rng;
+use std::default::Default;
+
+pub const RACK_SIZE: usize = 7;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Controller {
+    Human,
+    Computer(Level),
+}
+
+#[derive(Clone)]
+pub struct Player {
+    pub name: String,
+    pub tiles: Vec<Tile>,
+    pub score: u32,
+    pub controller: Controller,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player {
+    pub fn new() -> Player {
+        Player {
+            name: String::new(),
+            tiles: vec![],
+            score: 0,
+            controller: Controller::Human,
+        }
+    }
+
+    /// A player in `seat` named after it, e.g. "Player 1".
+    pub fn seated(seat: usize) -> Player {
+        Player {
+            name: format!("Player {}", seat + 1),
+            ..Player::new()
+        }
+    }
+
+    pub fn is_computer(&self) -> bool {
+        matches!(self.controller, Controller::Computer(_))
+    }
+
+    pub fn add_tile(&mut self, tile: Tile) {
+        self.tiles.push(tile);
+    }
+
+    pub fn remove_tile(&mut self, tile: Tile) {
+        self.tiles.retain(|t| *t != tile);
+    }
+
+    pub fn rack_value(&self) -> u32 {
+        self.tiles.iter().map(|tile| tile.get_value() as u32).sum()
+    }
+
+    /// Draws from `bag` until the rack is full or the bag runs out, and
+    /// returns the number of tiles drawn.
+    pub fn fill_tiles<R: Rng + ?Sized>(&mut self, bag: &mut Vec<Tile>, rng: &mut R) -> usize {
+        let mut drawn = 0;
+
+        while self.tiles.len() < RACK_SIZE && !bag.is_empty() {
+            let i = rng.gen_range(0..bag.len());
+            let tile = bag.remove(i);
+            self.add_tile(tile);
+            drawn += 1;
+        }
+
+        drawn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn blanks_are_worth_nothing_on_the_rack() {
+        let mut player = Player::new();
+        player.tiles = vec![Tile::Q, Tile::Blank, Tile::E];
+        assert_eq!(player.rack_value(), 11);
+    }
+
+    #[test]
+    fn fills_until_the_bag_runs_out() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut player = Player::new();
+
+        let mut bag = vec![Tile::A; 10];
+        assert_eq!(player.fill_tiles(&mut bag, &mut rng), RACK_SIZE);
+        assert_eq!(bag.len(), 3);
+
+        player.tiles.truncate(2);
+        assert_eq!(player.fill_tiles(&mut bag, &mut rng), 3);
+        assert_eq!(player.tiles.len(), 5);
+        assert_eq!(player.fill_tiles(&mut bag, &mut rng), 0);
+    }
+}
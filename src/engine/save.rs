@@ -0,0 +1,503 @@
+//! A plain text save format.
+//!
+//! ```text
+//! scrabble-save 3
+//! seed 42
+//! score 35
+//! current 1
+//! scoreless 0
+//! finished 0
+//! challenge double
+//! bag AABDE...
+//! players 2
+//! player human 20 EIRST?A Alice
+//! player computer expert 15 NOQ Player 2
+//! board
+//! ...............   (15 rows, `.` empty, lowercase for blanks)
+//! history 2
+//! turn 0 ACTEIRS 20 place 7,6,C 7,7,A 7,8,T words cat
+//! turn 1 NOQUVWX 0 exchange UVWX
+//! ```
+//!
+//! Racks and the bag use `?` for a blank and `-` when empty. Version 1
+//! saves have no `challenge` line and load with `ChallengeRule::Void`.
+//! Saves before version 3 have two players and no names.
+
+use ::rand::{rngs::StdRng, SeedableRng};
+use std::fmt::{self, Write};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::board::*;
+use super::bot::Level;
+use super::lexicon::*;
+use super::player::*;
+use super::state::*;
+use super::tile::*;
+use super::turn::*;
+
+pub const SAVE_VERSION: u32 = 3;
+const HEADER: &str = "scrabble-save";
+const LEVELS: [Level; 3] = [Level::Beginner, Level::Intermediate, Level::Expert];
+const RULES: [ChallengeRule; 4] = [
+    ChallengeRule::Void,
+    ChallengeRule::Single,
+    ChallengeRule::Double,
+    ChallengeRule::FivePoint,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseSaveError {
+    pub line: usize,
+}
+
+impl fmt::Display for ParseSaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid save file on line {}.", self.line)
+    }
+}
+
+impl std::error::Error for ParseSaveError {}
+
+impl State {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_save())
+    }
+
+    /// Loads a game saved with `save`, checking words against SOWPODS.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<State> {
+        let text = fs::read_to_string(path)?;
+        State::from_save(&text, Lexicon::sowpods())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the game in the save format. Tiles placed but not submitted
+    /// are saved on the rack.
+    pub fn to_save(&self) -> String {
+        let mut state = self.clone();
+        state.board.recall(&mut state.players[state.current]);
+
+        let mut out = String::new();
+        writeln!(out, "{} {}", HEADER, SAVE_VERSION).unwrap();
+        writeln!(out, "seed {}", state.seed).unwrap();
+        writeln!(out, "score {}", state.score).unwrap();
+        writeln!(out, "current {}", state.current).unwrap();
+        writeln!(out, "scoreless {}", state.scoreless_turns).unwrap();
+        writeln!(out, "finished {}", state.finished as u8).unwrap();
+        writeln!(
+            out,
+            "challenge {}",
+            state.challenge_rule.to_string().to_lowercase()
+        )
+        .unwrap();
+        writeln!(out, "bag {}", tiles_field(&state.tile_bag)).unwrap();
+        writeln!(out, "players {}", state.players.len()).unwrap();
+
+        for player in &state.players {
+            let controller = match player.controller {
+                Controller::Human => "human".to_string(),
+                Controller::Computer(level) => {
+                    format!("computer {}", level.to_string().to_lowercase())
+                }
+            };
+            writeln!(
+                out,
+                "player {} {} {} {}",
+                controller,
+                player.score,
+                tiles_field(&player.tiles),
+                player.name
+            )
+            .unwrap();
+        }
+
+        writeln!(out, "board").unwrap();
+        for row in state.board.squares() {
+            let row: String = row
+                .iter()
+                .map(|square| square.map_or('.', |tile| tile.to_char()))
+                .collect();
+            writeln!(out, "{}", row).unwrap();
+        }
+
+        writeln!(out, "history {}", state.history.len()).unwrap();
+        for record in &state.history {
+            write!(
+                out,
+                "turn {} {} {} ",
+                record.player,
+                tiles_field(&record.rack),
+                record.points
+            )
+            .unwrap();
+
+            match &record.play {
+                Play::Place { tiles, words } => {
+                    write!(out, "place").unwrap();
+                    for &(row, col, tile) in tiles {
+                        write!(out, " {},{},{}", row, col, tile.to_char()).unwrap();
+                    }
+                    writeln!(out, " words {}", words.join(" ")).unwrap();
+                }
+                Play::Pass => writeln!(out, "pass").unwrap(),
+                Play::Exchange(returned) => {
+                    writeln!(out, "exchange {}", tiles_field(returned)).unwrap()
+                }
+                Play::Withdrawn => writeln!(out, "withdrawn").unwrap(),
+                Play::FailedChallenge { lost_turn } => {
+                    let turn = if *lost_turn { "lost" } else { "kept" };
+                    writeln!(out, "failed-challenge {}", turn).unwrap()
+                }
+                Play::ChallengeBonus => writeln!(out, "challenge-bonus").unwrap(),
+            }
+        }
+
+        out
+    }
+
+    /// Reads a game written by `to_save`.
+    ///
+    /// The random generator cannot be saved, so draws after loading come
+    /// from one seeded with the game's seed and the number of turns played.
+    /// Loading the same save always continues the same way.
+    pub fn from_save(text: &str, words: Lexicon) -> Result<State, ParseSaveError> {
+        let mut reader = Reader {
+            lines: text.lines(),
+            line: 0,
+        };
+
+        let version: u32 = reader.field(HEADER)?.parse().map_err(|_| reader.error())?;
+        if version == 0 || version > SAVE_VERSION {
+            return Err(reader.error());
+        }
+
+        let seed = reader.parse_field("seed")?;
+        let score = reader.parse_field("score")?;
+        let current = reader.parse_field("current")?;
+        let scoreless_turns = reader.parse_field("scoreless")?;
+        let finished = match reader.field("finished")? {
+            "0" => false,
+            "1" => true,
+            _ => return Err(reader.error()),
+        };
+        let challenge_rule = if version >= 2 {
+            let rule = reader.field("challenge")?;
+            *RULES
+                .iter()
+                .find(|r| r.to_string().eq_ignore_ascii_case(rule))
+                .ok_or_else(|| reader.error())?
+        } else {
+            ChallengeRule::Void
+        };
+        let bag = reader.field("bag")?;
+        let tile_bag = reader.tiles(bag)?;
+
+        let count = if version >= 3 {
+            reader.parse_field("players")?
+        } else {
+            MIN_PLAYERS
+        };
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&count) {
+            return Err(reader.error());
+        }
+
+        let mut players = vec![];
+        while players.len() < count {
+            let tokens = reader.next()?;
+            let (controller, score, rack, name) = match tokens.as_slice() {
+                ["player", "human", score, rack, name @ ..] => {
+                    (Controller::Human, *score, *rack, name)
+                }
+                ["player", "computer", level, score, rack, name @ ..] => {
+                    let level = LEVELS
+                        .iter()
+                        .find(|l| l.to_string().eq_ignore_ascii_case(level))
+                        .ok_or_else(|| reader.error())?;
+                    (Controller::Computer(*level), *score, *rack, name)
+                }
+                _ => return Err(reader.error()),
+            };
+            if (version >= 3) == name.is_empty() {
+                return Err(reader.error());
+            }
+
+            let mut player = Player::seated(players.len());
+            if !name.is_empty() {
+                player.name = name.join(" ");
+            }
+            player.tiles = reader.tiles(rack)?;
+            player.score = score.parse().map_err(|_| reader.error())?;
+            player.controller = controller;
+            players.push(player);
+        }
+        if current >= players.len() {
+            return Err(reader.error());
+        }
+
+        reader.expect("board")?;
+        let mut board = Board::new();
+        for row in 0..15 {
+            let tokens = reader.next()?;
+            let squares: Vec<char> = match tokens.as_slice() {
+                [squares] => squares.chars().collect(),
+                _ => return Err(reader.error()),
+            };
+            if squares.len() != 15 {
+                return Err(reader.error());
+            }
+
+            for (col, &c) in squares.iter().enumerate() {
+                if c == '.' {
+                    continue;
+                }
+                let tile = PlacedTile::from_char(c).ok_or_else(|| reader.error())?;
+                board.place(row, col, tile.rack_tile());
+                board.assign_blank(row, col, tile.letter());
+            }
+        }
+        board.commit();
+
+        let count: usize = reader.parse_field("history")?;
+        let mut history = Vec::new();
+        for _ in 0..count {
+            let record = reader.record()?;
+            if record.player >= players.len() {
+                return Err(reader.error());
+            }
+            history.push(record);
+        }
+
+        let rng = StdRng::seed_from_u64(seed ^ (history.len() as u64).rotate_left(32));
+        Ok(State {
+            tile_bag,
+            score,
+            words,
+            board,
+            players,
+            current,
+            scoreless_turns,
+            finished,
+            challenge_rule,
+            history,
+            seed,
+            rng,
+        })
+    }
+}
+
+struct Reader<'a> {
+    lines: std::str::Lines<'a>,
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn error(&self) -> ParseSaveError {
+        ParseSaveError { line: self.line }
+    }
+
+    fn next(&mut self) -> Result<Vec<&'a str>, ParseSaveError> {
+        self.line += 1;
+        match self.lines.next() {
+            Some(line) => Ok(line.split_whitespace().collect()),
+            None => Err(self.error()),
+        }
+    }
+
+    fn expect(&mut self, key: &str) -> Result<(), ParseSaveError> {
+        match self.next()?.as_slice() {
+            [k] if *k == key => Ok(()),
+            _ => Err(self.error()),
+        }
+    }
+
+    /// The value of a `key value` line.
+    fn field(&mut self, key: &str) -> Result<&'a str, ParseSaveError> {
+        match self.next()?.as_slice() {
+            [k, value] if *k == key => Ok(value),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn parse_field<T: std::str::FromStr>(&mut self, key: &str) -> Result<T, ParseSaveError> {
+        self.field(key)?.parse().map_err(|_| self.error())
+    }
+
+    fn tiles(&self, text: &str) -> Result<Vec<Tile>, ParseSaveError> {
+        if text == "-" {
+            return Ok(vec![]);
+        }
+
+        parse_tiles(text).ok_or_else(|| self.error())
+    }
+
+    fn record(&mut self) -> Result<Record, ParseSaveError> {
+        let tokens = self.next()?;
+        let (player, rack, points, play) = match tokens.as_slice() {
+            ["turn", player, rack, points, play @ ..] => (player, rack, points, play),
+            _ => return Err(self.error()),
+        };
+
+        let play = match play {
+            ["pass"] => Play::Pass,
+            ["withdrawn"] => Play::Withdrawn,
+            ["failed-challenge", "lost"] => Play::FailedChallenge { lost_turn: true },
+            ["failed-challenge", "kept"] => Play::FailedChallenge { lost_turn: false },
+            ["challenge-bonus"] => Play::ChallengeBonus,
+            ["exchange", returned] => Play::Exchange(self.tiles(returned)?),
+            ["place", rest @ ..] => {
+                let split = rest
+                    .iter()
+                    .position(|&t| t == "words")
+                    .ok_or_else(|| self.error())?;
+                let tiles = rest[..split]
+                    .iter()
+                    .map(|tile| parse_placed(tile))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| self.error())?;
+                let words = rest[split + 1..].iter().map(|w| w.to_string()).collect();
+                Play::Place { tiles, words }
+            }
+            _ => return Err(self.error()),
+        };
+
+        Ok(Record {
+            player: player.parse().map_err(|_| self.error())?,
+            rack: self.tiles(rack)?,
+            play,
+            points: points.parse().map_err(|_| self.error())?,
+        })
+    }
+}
+
+/// Tiles as written by `tiles_to_string`, or `-` for none.
+fn tiles_field(tiles: &[Tile]) -> String {
+    if tiles.is_empty() {
+        return "-".to_string();
+    }
+    tiles_to_string(tiles)
+}
+
+/// A `row,col,letter` tile of a placement.
+fn parse_placed(text: &str) -> Option<(usize, usize, PlacedTile)> {
+    let mut parts = text.split(',');
+    let row: usize = parts.next()?.parse().ok()?;
+    let col: usize = parts.next()?.parse().ok()?;
+    let mut letter = parts.next()?.chars();
+    let tile = PlacedTile::from_char(letter.next()?)?;
+
+    if row >= 15 || col >= 15 || letter.next().is_some() || parts.next().is_some() {
+        return None;
+    }
+
+    Some((row, col, tile))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::testing::*;
+
+    fn reload(state: &State) -> State {
+        State::from_save(&state.to_save(), state.words.clone())
+            .ok()
+            .unwrap()
+    }
+
+    #[test]
+    fn round_trips() {
+        for (seed, turns) in [(1, 0), (2, 8), (3, 200)] {
+            let state = bot_game(seed, turns);
+            let loaded = reload(&state);
+
+            assert_eq!(loaded.to_save(), state.to_save());
+            assert_eq!(loaded.board.squares(), state.board.squares());
+            assert_eq!(loaded.history, state.history);
+            assert_eq!(loaded.finished, state.finished);
+            for (a, b) in loaded.players.iter().zip(&state.players) {
+                assert_eq!((a.score, &a.tiles), (b.score, &b.tiles));
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_names_and_placed_tiles_on_the_rack() {
+        let mut state = bot_game(4, 4);
+        state.players[1].name = "Ada Lovelace".to_string();
+        let rack = state.player().tiles.clone();
+        let tile = rack[0];
+        state.place_tile(0, 0, 0);
+
+        let loaded = reload(&state);
+        assert_eq!(loaded.players[1].name, "Ada Lovelace");
+        assert!(loaded.player().tiles.contains(&tile));
+        assert_eq!(loaded.player().tiles.len(), rack.len());
+        assert!(loaded.board.placed.is_empty());
+    }
+
+    #[test]
+    fn continues_the_same_way() {
+        let state = bot_game(5, 6);
+        let mut a = reload(&state);
+        let mut b = reload(&state);
+
+        a.act(Action::Exchange(vec![0, 1, 2])).unwrap();
+        b.act(Action::Exchange(vec![0, 1, 2])).unwrap();
+        assert_eq!(a.to_save(), b.to_save());
+    }
+
+    #[test]
+    fn reads_older_versions() {
+        let text = State::new(Some(6)).to_save();
+        let old: Vec<String> = text
+            .lines()
+            .filter(|line| !line.starts_with("players ") && !line.starts_with("challenge "))
+            .map(|line| match line {
+                "scrabble-save 3" => "scrabble-save 1".to_string(),
+                line if line.starts_with("player ") => {
+                    line.rsplitn(3, ' ').nth(2).unwrap().to_string()
+                }
+                line => line.to_string(),
+            })
+            .collect();
+
+        let loaded = State::from_save(&old.join("\n"), Lexicon::sowpods())
+            .ok()
+            .unwrap();
+        assert_eq!(loaded.players.len(), 2);
+        assert_eq!(loaded.players[1].name, "Player 2");
+        assert_eq!(loaded.challenge_rule, ChallengeRule::Void);
+    }
+
+    #[test]
+    fn reports_the_bad_line() {
+        let text = bot_game(7, 6).to_save();
+        let error = |from: &str, to: &str| {
+            State::from_save(&text.replacen(from, to, 1), Lexicon::sowpods()).err()
+        };
+
+        assert_eq!(
+            error("scrabble-save 3", "scrabble-save 9"),
+            Some(ParseSaveError { line: 1 })
+        );
+        assert_eq!(error("seed", "sed"), Some(ParseSaveError { line: 2 }));
+        assert_eq!(
+            error("players 2", "players 5"),
+            Some(ParseSaveError { line: 9 })
+        );
+
+        let history = text.lines().position(|l| l.starts_with("history")).unwrap();
+        assert_eq!(
+            error("\nturn 0", "\nturn 0 x"),
+            Some(ParseSaveError { line: history + 2 })
+        );
+        assert_eq!(
+            error("\nturn 0", "\nturn 2"),
+            Some(ParseSaveError { line: history + 2 })
+        );
+        assert_eq!(
+            error("\nhistory 6", "\nhistory 1000000000000"),
+            Some(ParseSaveError { line: history + 8 })
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use super::board::{premium, Grid, Premium};
+use super::moves::*;
+use super::player::RACK_SIZE;
+use std::fmt;
+
+pub const BINGO_BONUS: u32 = 50;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordScore {
+    pub word: String,
+    pub score: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MoveScore {
+    pub words: Vec<WordScore>,
+    pub bingo: bool,
+    pub total: u32,
+}
+
+impl fmt::Display for MoveScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let words: Vec<String> = self
+            .words
+            .iter()
+            .map(|w| format!("{} {}", w.word.to_uppercase(), w.score))
+            .collect();
+
+        write!(f, "{}", words.join(", "))?;
+        if self.bingo {
+            write!(f, " + {} bingo", BINGO_BONUS)?;
+        }
+        write!(f, " = {}", self.total)
+    }
+}
+
+/// Scores every word formed by a move.
+///
+/// Premium squares only count when they are in `placed`, i.e. covered
+/// this turn.
+pub fn score_move(board: &Grid, words: &[Word], placed: &[(usize, usize)]) -> MoveScore {
+    let words: Vec<WordScore> = words
+        .iter()
+        .map(|word| WordScore {
+            word: word.text.clone(),
+            score: score_word(board, word, placed),
+        })
+        .collect();
+
+    let bingo = placed.len() == RACK_SIZE;
+    let mut total = words.iter().map(|w| w.score).sum();
+    if bingo {
+        total += BINGO_BONUS;
+    }
+
+    MoveScore {
+        words,
+        bingo,
+        total,
+    }
+}
+
+fn score_word(board: &Grid, word: &Word, placed: &[(usize, usize)]) -> u32 {
+    let mut sum = 0;
+    let mut multiplier = 1;
+
+    for &(row, col) in &word.squares {
+        let value = board[row][col].map_or(0, |tile| tile.get_value() as u32);
+
+        if !placed.contains(&(row, col)) {
+            sum += value;
+            continue;
+        }
+
+        match premium(row, col) {
+            Some(Premium::DoubleLetter) => sum += value * 2,
+            Some(Premium::TripleLetter) => sum += value * 3,
+            Some(Premium::DoubleWord) => {
+                sum += value;
+                multiplier *= 2;
+            }
+            Some(Premium::TripleWord) => {
+                sum += value;
+                multiplier *= 3;
+            }
+            None => sum += value,
+        }
+    }
+
+    sum * multiplier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::testing::write;
+    use crate::engine::tile::*;
+
+    fn score(grid: &Grid, placed: &[(usize, usize)], direction: Direction) -> MoveScore {
+        score_move(grid, &formed_words(grid, placed, direction), placed)
+    }
+
+    #[test]
+    fn center_doubles_the_first_word() {
+        let mut grid = [[None; 15]; 15];
+        let placed = write(&mut grid, 7, 6, Direction::Across, "cat");
+        assert_eq!(score(&grid, &placed, Direction::Across).total, 10);
+    }
+
+    #[test]
+    fn premiums_only_count_when_covered() {
+        let mut grid = [[None; 15]; 15];
+        write(&mut grid, 7, 6, Direction::Across, "cat");
+        let placed = write(&mut grid, 7, 9, Direction::Across, "s");
+        assert_eq!(score(&grid, &placed, Direction::Across).total, 6);
+    }
+
+    #[test]
+    fn letter_and_word_premiums() {
+        // Q on a triple word, Z on a double letter.
+        let mut grid = [[None; 15]; 15];
+        let placed = write(&mut grid, 0, 0, Direction::Across, "quiz");
+        assert_eq!(score(&grid, &placed, Direction::Across).total, 96);
+
+        // Z on a triple letter.
+        let mut grid = [[None; 15]; 15];
+        let placed = write(&mut grid, 1, 4, Direction::Across, "az");
+        assert_eq!(score(&grid, &placed, Direction::Across).total, 31);
+    }
+
+    #[test]
+    fn blanks_score_nothing() {
+        let mut grid = [[None; 15]; 15];
+        let placed = write(&mut grid, 1, 4, Direction::Across, "az");
+        grid[1][5] = Some(PlacedTile::Blank(Tile::Z));
+        assert_eq!(score(&grid, &placed, Direction::Across).total, 1);
+    }
+
+    #[test]
+    fn bingo_adds_fifty() {
+        let mut grid = [[None; 15]; 15];
+        let placed = write(&mut grid, 7, 7, Direction::Across, "quixote");
+        let score = score(&grid, &placed, Direction::Across);
+        assert!(score.bingo);
+        // Q on the center star, O on a double letter.
+        let letters = 10 + 1 + 1 + 8 + 2 + 1 + 1;
+        assert_eq!(score.total, letters * 2 + BINGO_BONUS);
+    }
+}
@@ -0,0 +1,202 @@
+use ::rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use super::movegen::*;
+use super::state::*;
+use super::turn::*;
+
+/// How many of the best scoring moves are simulated by default.
+pub const SIM_CANDIDATES: usize = 10;
+/// Playouts per candidate by default.
+pub const SIM_ITERATIONS: usize = 40;
+/// Moves played after the candidate in each playout by default.
+pub const SIM_PLIES: usize = 2;
+
+/// A simulated candidate and the average change in spread it led to.
+#[derive(Clone, Debug)]
+pub struct SimResult {
+    pub candidate: Candidate,
+    pub mean: f32,
+    pub iterations: usize,
+}
+
+/// Candidate moves being played out on worker threads. Playouts redraw the
+/// other players' racks from the tiles the current player cannot see, so
+/// the result does not depend on hidden information.
+///
+/// Dropping a simulation stops it.
+pub struct Simulation {
+    candidates: Arc<Vec<Candidate>>,
+    tallies: Arc<Mutex<Vec<(f32, usize)>>>,
+    done: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    total: usize,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Simulation {
+    /// Plays each of `candidates` out `iterations` times, `plies` moves
+    /// deep, for the current player of `state`.
+    pub fn start(
+        state: &State,
+        candidates: Vec<Candidate>,
+        iterations: usize,
+        plies: usize,
+        seed: u64,
+    ) -> Simulation {
+        let total = candidates.len() * iterations;
+        let candidates = Arc::new(candidates);
+        let tallies = Arc::new(Mutex::new(vec![(0.0, 0); candidates.len()]));
+        let next = Arc::new(AtomicUsize::new(0));
+        let done = Arc::new(AtomicUsize::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let workers = (0..threads.min(total))
+            .map(|_| {
+                let state = state.clone();
+                let candidates = Arc::clone(&candidates);
+                let tallies = Arc::clone(&tallies);
+                let next = Arc::clone(&next);
+                let done = Arc::clone(&done);
+                let cancel = Arc::clone(&cancel);
+
+                thread::spawn(move || loop {
+                    let job = next.fetch_add(1, Ordering::Relaxed);
+                    if job >= total || cancel.load(Ordering::Relaxed) {
+                        return;
+                    }
+
+                    // Interleave candidates so partial results cover all of them.
+                    let index = job % candidates.len();
+                    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(job as u64));
+                    let outcome = playout(&state, &candidates[index], plies, &mut rng);
+
+                    let mut tallies = tallies.lock().unwrap();
+                    tallies[index].0 += outcome;
+                    tallies[index].1 += 1;
+                    done.fetch_add(1, Ordering::Relaxed);
+                })
+            })
+            .collect();
+
+        Simulation {
+            candidates,
+            tallies,
+            done,
+            cancel,
+            total,
+            workers,
+        }
+    }
+
+    /// The fraction of playouts finished, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+
+        self.done.load(Ordering::Relaxed) as f32 / self.total as f32
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.done.load(Ordering::Relaxed) >= self.total
+    }
+
+    /// The candidates played out so far, best average first.
+    pub fn results(&self) -> Vec<SimResult> {
+        let tallies = self.tallies.lock().unwrap();
+        let mut results: Vec<SimResult> = self
+            .candidates
+            .iter()
+            .zip(tallies.iter())
+            .filter(|(_, &(_, iterations))| iterations > 0)
+            .map(|(candidate, &(sum, iterations))| SimResult {
+                candidate: candidate.clone(),
+                mean: sum / iterations as f32,
+                iterations,
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.mean.total_cmp(&a.mean));
+        results
+    }
+}
+
+impl Drop for Simulation {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Plays `candidate` and then `plies` best scoring moves on a copy of
+/// `state` with the hidden racks redrawn. Returns how much the player's
+/// spread changed.
+fn playout<R: Rng>(state: &State, candidate: &Candidate, plies: usize, rng: &mut R) -> f32 {
+    let mut state = state.clone();
+    let me = state.current;
+    state.rng = StdRng::seed_from_u64(rng.gen());
+    state.challenge_rule = ChallengeRule::Void;
+
+    for (i, player) in state.players.iter_mut().enumerate() {
+        if i != me {
+            state.tile_bag.append(&mut player.tiles);
+        }
+    }
+    state.tile_bag.shuffle(rng);
+    for (i, player) in state.players.iter_mut().enumerate() {
+        if i != me {
+            player.fill_tiles(&mut state.tile_bag, rng);
+        }
+    }
+
+    let before = state.spread(me);
+    if !state.place_move(&candidate.tiles) || state.act(Action::Submit).is_err() {
+        return 0.0;
+    }
+
+    for _ in 0..plies {
+        if state.finished {
+            break;
+        }
+
+        let best = ranked(&state, 1).pop();
+        let played =
+            best.is_some_and(|c| state.place_move(&c.tiles)) && state.act(Action::Submit).is_ok();
+        if !played {
+            let _ = state.act(Action::Pass);
+        }
+    }
+
+    (state.spread(me) - before) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::bot::*;
+    use std::time::Duration;
+
+    #[test]
+    fn seeded_simulations_finish_sorted() {
+        let state = State::new(Some(5));
+        let mut candidates = Bot::new(Level::Expert, 5).candidates(&state);
+        candidates.truncate(4);
+
+        let sim = Simulation::start(&state, candidates, 3, 1, 5);
+        while !sim.is_finished() {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(sim.progress(), 1.0);
+        let results = sim.results();
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|r| r.iterations == 3));
+        assert!(results.windows(2).all(|pair| pair[0].mean >= pair[1].mean));
+    }
+}
@@ -0,0 +1,179 @@
+use super::board::*;
+use super::lexicon::*;
+use super::player::*;
+use super::tile::*;
+use super::turn::{ChallengeRule, Record};
+use ::rand::{self, rngs::StdRng, Rng, SeedableRng};
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 4;
+
+#[derive(Clone)]
+pub struct State {
+    pub tile_bag: Vec<Tile>,
+    pub score: u32,
+    pub words: Lexicon,
+    pub board: Board,
+    pub players: Vec<Player>,
+    pub current: usize,
+    pub scoreless_turns: usize,
+    pub finished: bool,
+    pub challenge_rule: ChallengeRule,
+    /// Every finished turn, oldest first.
+    pub history: Vec<Record>,
+    pub seed: u64,
+    pub(crate) rng: StdRng,
+}
+
+impl State {
+    /// Starts a new game. Games with the same `seed` draw the same tiles;
+    /// without one a random seed is picked.
+    pub fn new(seed: Option<u64>) -> State {
+        State::with_words(seed, Lexicon::sowpods())
+    }
+
+    pub fn with_words(seed: Option<u64>, words: Lexicon) -> State {
+        let players = (0..MIN_PLAYERS).map(Player::seated).collect();
+        State::with_players(seed, words, players)
+    }
+
+    /// Starts a game between `players`, dealing each of them a rack in
+    /// seat order.
+    ///
+    /// Panics unless there are `MIN_PLAYERS` to `MAX_PLAYERS` players.
+    pub fn with_players(seed: Option<u64>, words: Lexicon, mut players: Vec<Player>) -> State {
+        assert!((MIN_PLAYERS..=MAX_PLAYERS).contains(&players.len()));
+
+        let mut tile_bag = vec![];
+        let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for i in Tile::iter() {
+            let quantity = i.get_quantity();
+            for _ in 0..quantity {
+                tile_bag.push(*i);
+            }
+        }
+
+        for player in players.iter_mut() {
+            player.tiles.clear();
+            player.score = 0;
+            player.fill_tiles(&mut tile_bag, &mut rng);
+        }
+
+        State {
+            tile_bag,
+            score: 0,
+            words,
+            board: Board::new(),
+            players,
+            current: 0,
+            scoreless_turns: 0,
+            finished: false,
+            challenge_rule: ChallengeRule::default(),
+            history: vec![],
+            seed,
+            rng,
+        }
+    }
+
+    pub fn player(&self) -> &Player {
+        &self.players[self.current]
+    }
+
+    /// How far `player` is ahead of the best of the others.
+    pub fn spread(&self, player: usize) -> i64 {
+        let best_other = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != player)
+            .map(|(_, p)| p.score)
+            .max()
+            .unwrap_or(0);
+
+        self.players[player].score as i64 - best_other as i64
+    }
+
+    /// Moves the rack tile at `index` onto an empty square.
+    pub fn place_tile(&mut self, index: usize, row: usize, col: usize) -> bool {
+        let player = &mut self.players[self.current];
+        if index >= player.tiles.len() || !self.board.place(row, col, player.tiles[index]) {
+            return false;
+        }
+
+        player.tiles.remove(index);
+        true
+    }
+
+    /// Places a whole move from the current rack, e.g. one picked by a bot.
+    /// Nothing is placed if a tile is missing from the rack.
+    pub fn place_move(&mut self, tiles: &[(usize, usize, PlacedTile)]) -> bool {
+        for &(row, col, tile) in tiles {
+            let index = self
+                .player()
+                .tiles
+                .iter()
+                .position(|&t| t == tile.rack_tile());
+
+            match index {
+                Some(index) if self.place_tile(index, row, col) => {
+                    self.board.assign_blank(row, col, tile.letter());
+                }
+                _ => {
+                    self.board.recall(&mut self.players[self.current]);
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    pub fn take_back(&mut self, row: usize, col: usize) {
+        self.board
+            .take_back(row, col, &mut self.players[self.current]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deals_every_seat() {
+        let players = (0..MAX_PLAYERS).map(Player::seated).collect();
+        let state = State::with_players(Some(1), Lexicon::from_words(["cat"]), players);
+
+        assert!(state.players.iter().all(|p| p.tiles.len() == RACK_SIZE));
+        assert_eq!(state.tile_bag.len(), 100 - MAX_PLAYERS * RACK_SIZE);
+        assert_eq!(state.players[3].name, "Player 4");
+    }
+
+    #[test]
+    fn seeds_repeat_the_deal() {
+        let a = State::new(Some(42));
+        let b = State::new(Some(42));
+
+        assert_eq!(a.players[0].tiles, b.players[0].tiles);
+        assert_eq!(a.tile_bag, b.tile_bag);
+    }
+
+    #[test]
+    fn spreads_against_the_best_other() {
+        let players = (0..3).map(Player::seated).collect();
+        let mut state = State::with_players(Some(1), Lexicon::from_words(["cat"]), players);
+        for (player, score) in state.players.iter_mut().zip([30, 50, 20]) {
+            player.score = score;
+        }
+
+        assert_eq!(state.spread(0), -20);
+        assert_eq!(state.spread(1), 20);
+    }
+
+    #[test]
+    #[should_panic]
+    fn needs_two_players() {
+        State::with_players(None, Lexicon::from_words(["cat"]), vec![Player::new()]);
+    }
+}
@@ -0,0 +1,99 @@
+//! Helpers shared by the engine's tests.
+
+use super::board::*;
+use super::bot::*;
+use super::moves::Direction;
+use super::state::*;
+use super::tile::*;
+use super::turn::Action;
+
+/// Writes `word` onto `grid` from `(row, col)` and returns its squares.
+pub fn write(
+    grid: &mut Grid,
+    row: usize,
+    col: usize,
+    direction: Direction,
+    word: &str,
+) -> Vec<(usize, usize)> {
+    word.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let (r, c2) = match direction {
+                Direction::Across => (row, col + i),
+                Direction::Down => (row + i, col),
+            };
+            grid[r][c2] = Some(PlacedTile::new(Tile::from(c)));
+            (r, c2)
+        })
+        .collect()
+}
+
+/// Plays a seeded game between intermediate bots for up to `turns` turns.
+pub fn bot_game(seed: u64, turns: usize) -> State {
+    let mut played = 0;
+    play_until(seed, |_| {
+        played += 1;
+        played > turns
+    })
+}
+
+/// Plays a seeded game between intermediate bots until `stop` or the end
+/// of the game.
+pub fn play_until<F: FnMut(&State) -> bool>(seed: u64, mut stop: F) -> State {
+    let mut state = State::new(Some(seed));
+    let mut bots: Vec<Bot> = (0..state.players.len())
+        .map(|i| Bot::new(Level::Intermediate, seed + i as u64))
+        .collect();
+
+    while !state.finished && !stop(&state) {
+        let action = match bots[state.current].decide(&state) {
+            Decision::Play(candidate) => {
+                assert!(state.place_move(&candidate.tiles));
+                Action::Submit
+            }
+            Decision::Exchange(indices) => Action::Exchange(indices),
+            Decision::Pass => Action::Pass,
+            Decision::Challenge => Action::Challenge,
+            Decision::Accept => Action::Accept,
+        };
+        state.act(action).unwrap();
+    }
+
+    state
+}
+
+/// Swaps `player`'s rack for `letters`, with `?` for a blank. Tiles held
+/// by other players are swapped for ones from the bag.
+pub fn deal(state: &mut State, player: usize, letters: &str) {
+    let mut rack = std::mem::take(&mut state.players[player].tiles);
+    state.tile_bag.append(&mut rack);
+
+    for c in letters.chars() {
+        let tile = if c == '?' { Tile::Blank } else { Tile::from(c) };
+        if !state.tile_bag.contains(&tile) {
+            let holder = state
+                .players
+                .iter_mut()
+                .find(|p| p.tiles.contains(&tile))
+                .unwrap();
+            let i = holder.tiles.iter().position(|&t| t == tile).unwrap();
+            let j = state.tile_bag.iter().position(|&t| t != tile).unwrap();
+            std::mem::swap(&mut holder.tiles[i], &mut state.tile_bag[j]);
+        }
+
+        let i = state.tile_bag.iter().position(|&t| t == tile).unwrap();
+        rack.push(state.tile_bag.swap_remove(i));
+    }
+    state.players[player].tiles = rack;
+}
+
+/// Places `word` across from `(row, col)` using tiles from the current
+/// rack.
+pub fn place(state: &mut State, row: usize, col: usize, word: &str) {
+    let tiles: Vec<(usize, usize, PlacedTile)> = word
+        .chars()
+        .enumerate()
+        .map(|(i, c)| (row, col + i, PlacedTile::new(Tile::from(c))))
+        .collect();
+    assert!(state.place_move(&tiles));
+}
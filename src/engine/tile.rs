@@ -0,0 +1,211 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[rustfmt::skip]
+pub enum Tile {
+    A, B, C, D, E, F, G,
+    H, I, J, K, L, M, N,
+    O, P, Q, R, S, T, U,
+    V, W, X, Y, Z, Blank,
+}
+
+impl Tile {
+    pub fn get_value(&self) -> u8 {
+        use Tile::*;
+
+        match self {
+            Blank => 0,
+            A | E | I | L | N | O | R | S | T | U => 1,
+            D | G => 2,
+            B | C | M | P => 3,
+            F | H | V | W | Y => 4,
+            K => 5,
+            J | X => 8,
+            Q | Z => 10,
+        }
+    }
+
+    pub fn get_quantity(&self) -> u8 {
+        use Tile::*;
+
+        match self {
+            Z | X | Q | K | J => 1,
+            Y | W | V | P | M | H | F | C | B | Blank => 2,
+            G => 3,
+            U | S | L | D => 4,
+            T | R | N => 6,
+            O => 8,
+            I | A => 9,
+            E => 12,
+        }
+    }
+
+    /// Position in the alphabet, with `Blank` last.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(i: usize) -> Tile {
+        *Tile::iter().nth(i).unwrap_or(&Tile::Blank)
+    }
+
+    pub fn iter() -> std::slice::Iter<'static, Tile> {
+        use Tile::*;
+
+        [
+            A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, Blank,
+        ]
+        .iter()
+    }
+}
+
+/// A tile on the board. A blank carries the letter it stands for and
+/// is worth nothing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PlacedTile {
+    Letter(Tile),
+    Blank(Tile),
+}
+
+impl PlacedTile {
+    pub fn new(tile: Tile) -> PlacedTile {
+        match tile {
+            Tile::Blank => PlacedTile::Blank(Tile::Blank),
+            tile => PlacedTile::Letter(tile),
+        }
+    }
+
+    pub fn letter(&self) -> Tile {
+        match *self {
+            PlacedTile::Letter(tile) | PlacedTile::Blank(tile) => tile,
+        }
+    }
+
+    /// The tile that goes back on the rack when this one is picked up.
+    pub fn rack_tile(&self) -> Tile {
+        match *self {
+            PlacedTile::Letter(tile) => tile,
+            PlacedTile::Blank(_) => Tile::Blank,
+        }
+    }
+
+    pub fn get_value(&self) -> u8 {
+        match self {
+            PlacedTile::Letter(tile) => tile.get_value(),
+            PlacedTile::Blank(_) => 0,
+        }
+    }
+
+    /// Uppercase for a letter tile, lowercase for a blank.
+    pub fn to_char(&self) -> char {
+        let letter: &str = (&self.letter()).into();
+        let letter = letter.chars().next().unwrap_or('?');
+
+        match self {
+            PlacedTile::Letter(_) => letter,
+            PlacedTile::Blank(_) => letter.to_ascii_lowercase(),
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<PlacedTile> {
+        if c.is_ascii_uppercase() {
+            Some(PlacedTile::Letter(Tile::from(c)))
+        } else if c.is_ascii_lowercase() {
+            Some(PlacedTile::Blank(Tile::from(c)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Tiles as uppercase letters, with `?` for a blank.
+pub fn tiles_to_string(tiles: &[Tile]) -> String {
+    tiles
+        .iter()
+        .map(|tile| match tile {
+            Tile::Blank => "?",
+            tile => tile.into(),
+        })
+        .collect()
+}
+
+/// Reads tiles written by `tiles_to_string`.
+pub fn parse_tiles(text: &str) -> Option<Vec<Tile>> {
+    text.chars()
+        .map(|c| match c {
+            '?' => Some(Tile::Blank),
+            c if c.is_ascii_uppercase() => Some(Tile::from(c)),
+            _ => None,
+        })
+        .collect()
+}
+
+impl From<char> for Tile {
+    fn from(c: char) -> Tile {
+        use Tile::*;
+
+        match c.to_ascii_lowercase() {
+            'a' => A,
+            'b' => B,
+            'c' => C,
+            'd' => D,
+            'e' => E,
+            'f' => F,
+            'g' => G,
+            'h' => H,
+            'i' => I,
+            'j' => J,
+            'k' => K,
+            'l' => L,
+            'm' => M,
+            'n' => N,
+            'o' => O,
+            'p' => P,
+            'q' => Q,
+            'r' => R,
+            's' => S,
+            't' => T,
+            'u' => U,
+            'v' => V,
+            'w' => W,
+            'x' => X,
+            'y' => Y,
+            'z' => Z,
+            _ => Blank,
+        }
+    }
+}
+
+impl From<&Tile> for &str {
+    fn from(t: &Tile) -> &'static str {
+        use Tile::*;
+
+        match t {
+            A => "A",
+            B => "B",
+            C => "C",
+            D => "D",
+            E => "E",
+            F => "F",
+            G => "G",
+            H => "H",
+            I => "I",
+            J => "J",
+            K => "K",
+            L => "L",
+            M => "M",
+            N => "N",
+            O => "O",
+            P => "P",
+            Q => "Q",
+            R => "R",
+            S => "S",
+            T => "T",
+            U => "U",
+            V => "V",
+            W => "W",
+            X => "X",
+            Y => "Y",
+            Z => "Z",
+            _ => "",
+        }
+    }
+}
@@ -0,0 +1,794 @@
+use std::fmt;
+
+use super::board::*;
+use super::moves::*;
+use super::score::*;
+use super::state::*;
+use super::tile::*;
+
+pub const MIN_BAG_FOR_EXCHANGE: usize = 7;
+pub const MAX_SCORELESS_TURNS: usize = 6;
+/// Points for the challenged player when a challenge fails under
+/// `ChallengeRule::FivePoint`.
+pub const CHALLENGE_BONUS: i32 = 5;
+
+/// How words are checked. Except under `Void`, any play is accepted and the
+/// next player may challenge it. A play that uses the last tiles only ends
+/// the game once the next player accepts it or challenges it and fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ChallengeRule {
+    /// Plays with invalid words are refused.
+    #[default]
+    Void,
+    /// A phony is taken back and a failed challenge costs nothing.
+    Single,
+    /// A phony is taken back and a failed challenge loses the challenger's
+    /// turn.
+    Double,
+    /// A phony is taken back and a failed challenge gives the challenged
+    /// player `CHALLENGE_BONUS` points.
+    FivePoint,
+}
+
+impl ChallengeRule {
+    pub fn next(&self) -> ChallengeRule {
+        match self {
+            ChallengeRule::Void => ChallengeRule::Single,
+            ChallengeRule::Single => ChallengeRule::Double,
+            ChallengeRule::Double => ChallengeRule::FivePoint,
+            ChallengeRule::FivePoint => ChallengeRule::Void,
+        }
+    }
+}
+
+impl fmt::Display for ChallengeRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChallengeRule::Void => write!(f, "Void"),
+            ChallengeRule::Single => write!(f, "Single"),
+            ChallengeRule::Double => write!(f, "Double"),
+            ChallengeRule::FivePoint => write!(f, "5-point"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Submit,
+    Pass,
+    /// Rack indices of the tiles to return to the bag.
+    Exchange(Vec<usize>),
+    UndoPlacement,
+    /// Challenges the words of the previous play.
+    Challenge,
+    /// Lets a play that used the last tiles stand, ending the game.
+    Accept,
+}
+
+/// What was done on a finished turn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Play {
+    /// The tiles laid down and the words they formed, main word first.
+    Place {
+        tiles: Vec<(usize, usize, PlacedTile)>,
+        words: Vec<String>,
+    },
+    Pass,
+    /// The tiles returned to the bag.
+    Exchange(Vec<Tile>),
+    /// The player's previous play was challenged off the board. The record
+    /// takes its points back.
+    Withdrawn,
+    /// A challenge that found every word valid.
+    FailedChallenge {
+        lost_turn: bool,
+    },
+    /// Points for being challenged without cause.
+    ChallengeBonus,
+}
+
+impl fmt::Display for Play {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Play::Place { words, .. } => match words.first() {
+                Some(word) => write!(f, "{}", word.to_uppercase()),
+                None => write!(f, "play"),
+            },
+            Play::Pass => write!(f, "pass"),
+            Play::Exchange(returned) => write!(f, "exchange {}", returned.len()),
+            Play::Withdrawn => write!(f, "withdrawn"),
+            Play::FailedChallenge { .. } => write!(f, "failed challenge"),
+            Play::ChallengeBonus => write!(f, "challenge bonus"),
+        }
+    }
+}
+
+/// A finished turn, as kept in `State::history`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub player: usize,
+    /// The rack at the start of the turn.
+    pub rack: Vec<Tile>,
+    pub play: Play,
+    /// Negative when a withdrawn play's points are taken back.
+    pub points: i32,
+}
+
+/// What a successful action did.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Played(MoveScore),
+    Passed,
+    Exchanged(usize),
+    Recalled,
+    /// The phony words found by a challenge, empty when it failed.
+    Challenged(Vec<String>),
+    Accepted,
+}
+
+impl State {
+    pub fn act(&mut self, action: Action) -> Result<Outcome, MoveError> {
+        use Action::*;
+
+        if self.finished {
+            return Err(MoveError::GameOver);
+        }
+
+        if let Some(out) = self.going_out() {
+            match action {
+                Accept => {
+                    self.undo_placement();
+                    self.finish(Some(out));
+                    return Ok(Outcome::Accepted);
+                }
+                Challenge | UndoPlacement => {}
+                _ => return Err(MoveError::ChallengeOrAccept),
+            }
+        }
+
+        let rack = self.rack_at_turn_start();
+
+        match action {
+            Submit => {
+                let tiles = self.placed_tiles();
+                let score = self.submit()?;
+                let words = score.words.iter().map(|w| w.word.clone()).collect();
+                self.record(rack, Play::Place { tiles, words }, score.total as i32);
+                self.end_turn(score.total);
+                Ok(Outcome::Played(score))
+            }
+            Pass => {
+                self.undo_placement();
+                self.record(rack, Play::Pass, 0);
+                self.end_turn(0);
+                Ok(Outcome::Passed)
+            }
+            Exchange(indices) => {
+                self.undo_placement();
+                let returned = self.exchange(indices)?;
+                let count = returned.len();
+                self.record(rack, Play::Exchange(returned), 0);
+                self.end_turn(0);
+                Ok(Outcome::Exchanged(count))
+            }
+            UndoPlacement => {
+                self.undo_placement();
+                Ok(Outcome::Recalled)
+            }
+            Challenge => {
+                let phony = self.challenge()?;
+                Ok(Outcome::Challenged(phony))
+            }
+            Accept => Err(MoveError::NothingToChallenge),
+        }
+    }
+
+    /// The player whose play used the last tiles while the next player can
+    /// still challenge it.
+    pub fn going_out(&self) -> Option<usize> {
+        if self.challenge_rule == ChallengeRule::Void || self.finished || !self.tile_bag.is_empty()
+        {
+            return None;
+        }
+
+        match self.history.last() {
+            Some(Record {
+                player,
+                play: Play::Place { .. },
+                ..
+            }) if *player != self.current && self.players[*player].tiles.is_empty() => {
+                Some(*player)
+            }
+            _ => None,
+        }
+    }
+
+    /// The board as it was after the first `turns` turns of the history.
+    pub fn board_after(&self, turns: usize) -> Board {
+        let mut board = Board::new();
+
+        for (i, record) in self.history.iter().enumerate().take(turns) {
+            match &record.play {
+                Play::Place { tiles, .. } => {
+                    for &(row, col, tile) in tiles {
+                        board.place(row, col, tile.rack_tile());
+                        board.assign_blank(row, col, tile.letter());
+                    }
+                    board.commit();
+                }
+                Play::Withdrawn => {
+                    if let Some(tiles) = self.last_placement(record.player, i) {
+                        for &(row, col, _) in tiles {
+                            board.lift(row, col);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        board
+    }
+
+    /// Each player's points from the first `turns` turns of the history.
+    pub fn scores_after(&self, turns: usize) -> Vec<i32> {
+        let mut scores = vec![0; self.players.len()];
+        for record in self.history.iter().take(turns) {
+            scores[record.player] += record.points;
+        }
+        scores
+    }
+
+    /// The tiles of `player`'s last turn among the first `turns` records,
+    /// if that turn was a placement.
+    pub fn last_placement(
+        &self,
+        player: usize,
+        turns: usize,
+    ) -> Option<&[(usize, usize, PlacedTile)]> {
+        self.history[..turns]
+            .iter()
+            .rev()
+            .find(|record| record.player == player)
+            .and_then(|record| match &record.play {
+                Play::Place { tiles, .. } => Some(tiles.as_slice()),
+                _ => None,
+            })
+    }
+
+    fn submit(&mut self) -> Result<MoveScore, MoveError> {
+        let direction = self.board.validate_move()?;
+        let words = self.board.formed_words(direction);
+        if self.challenge_rule == ChallengeRule::Void {
+            check_words(&words, &self.words)?;
+        }
+        let score = self.board.score_move(&words);
+
+        let player = &mut self.players[self.current];
+        player.score += score.total;
+        player.fill_tiles(&mut self.tile_bag, &mut self.rng);
+        self.score += score.total;
+        self.board.commit();
+
+        Ok(score)
+    }
+
+    fn exchange(&mut self, mut indices: Vec<usize>) -> Result<Vec<Tile>, MoveError> {
+        let player = &mut self.players[self.current];
+        indices.sort_unstable();
+        indices.dedup();
+        indices.retain(|&i| i < player.tiles.len());
+
+        if indices.is_empty() {
+            return Err(MoveError::NothingToExchange);
+        }
+        if self.tile_bag.len() < MIN_BAG_FOR_EXCHANGE {
+            return Err(MoveError::BagTooSmall);
+        }
+
+        let returned: Vec<Tile> = indices
+            .iter()
+            .rev()
+            .map(|&i| player.tiles.remove(i))
+            .collect();
+        player.fill_tiles(&mut self.tile_bag, &mut self.rng);
+        self.tile_bag.extend_from_slice(&returned);
+
+        Ok(returned)
+    }
+
+    /// The current rack including any tiles already placed on the board.
+    fn rack_at_turn_start(&self) -> Vec<Tile> {
+        let mut rack = self.player().tiles.clone();
+        rack.extend(
+            self.placed_tiles()
+                .iter()
+                .map(|(_, _, tile)| tile.rack_tile()),
+        );
+        rack
+    }
+
+    fn placed_tiles(&self) -> Vec<(usize, usize, PlacedTile)> {
+        let mut tiles: Vec<(usize, usize, PlacedTile)> = self
+            .board
+            .placed
+            .iter()
+            .filter_map(|&(row, col)| self.board.get(row, col).map(|tile| (row, col, tile)))
+            .collect();
+        tiles.sort_by_key(|&(row, col, _)| (row, col));
+        tiles
+    }
+
+    fn record(&mut self, rack: Vec<Tile>, play: Play, points: i32) {
+        self.history.push(Record {
+            player: self.current,
+            rack,
+            play,
+            points,
+        });
+    }
+
+    /// The words of the last play that are not in the lexicon, or `None` if
+    /// the current player has nothing to challenge.
+    pub fn phonies(&self) -> Option<Vec<String>> {
+        if self.challenge_rule == ChallengeRule::Void {
+            return None;
+        }
+
+        match self.history.last() {
+            Some(Record {
+                player,
+                play: Play::Place { words, .. },
+                ..
+            }) if *player != self.current => Some(
+                words
+                    .iter()
+                    .filter(|word| !self.words.contains(word))
+                    .map(|word| word.to_uppercase())
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Checks the previous play's words. A phony is taken back: its tiles
+    /// return to its player's rack and the tiles drawn after it return to
+    /// the bag. Returns the phony words.
+    fn challenge(&mut self) -> Result<Vec<String>, MoveError> {
+        let phony = self.phonies().ok_or(MoveError::NothingToChallenge)?;
+        let out = self.going_out();
+        let last = self
+            .history
+            .last()
+            .cloned()
+            .ok_or(MoveError::NothingToChallenge)?;
+        let tiles = match &last.play {
+            Play::Place { tiles, .. } => tiles,
+            _ => return Err(MoveError::NothingToChallenge),
+        };
+        let challenged = last.player;
+        let challenger_rack = self.rack_at_turn_start();
+        self.undo_placement();
+
+        if !phony.is_empty() {
+            let points = last.points;
+            let rack = last.rack;
+
+            let mut drawn = self.players[challenged].tiles.clone();
+            let mut leave = rack.clone();
+            for &(row, col, tile) in tiles {
+                self.board.lift(row, col);
+                if let Some(i) = leave.iter().position(|&t| t == tile.rack_tile()) {
+                    leave.remove(i);
+                }
+            }
+            for tile in leave {
+                if let Some(i) = drawn.iter().position(|&t| t == tile) {
+                    drawn.remove(i);
+                }
+            }
+            self.tile_bag.append(&mut drawn);
+
+            let player = &mut self.players[challenged];
+            player.tiles = rack.clone();
+            player.score = (player.score as i32 - points).max(0) as u32;
+            self.score = (self.score as i32 - points).max(0) as u32;
+            self.scoreless_turns += 1;
+            self.history.push(Record {
+                player: challenged,
+                rack,
+                play: Play::Withdrawn,
+                points: -points,
+            });
+            return Ok(phony);
+        }
+
+        match self.challenge_rule {
+            ChallengeRule::Double => {
+                self.record(
+                    challenger_rack,
+                    Play::FailedChallenge { lost_turn: true },
+                    0,
+                );
+                if out.is_none() {
+                    self.end_turn(0);
+                }
+            }
+            ChallengeRule::FivePoint => {
+                self.players[challenged].score += CHALLENGE_BONUS as u32;
+                self.score += CHALLENGE_BONUS as u32;
+                self.history.push(Record {
+                    player: challenged,
+                    rack: self.players[challenged].tiles.clone(),
+                    play: Play::ChallengeBonus,
+                    points: CHALLENGE_BONUS,
+                });
+            }
+            _ => self.record(
+                challenger_rack,
+                Play::FailedChallenge { lost_turn: false },
+                0,
+            ),
+        }
+
+        // The play that went out stands.
+        if let Some(out) = out {
+            self.finish(Some(out));
+        }
+
+        Ok(phony)
+    }
+
+    fn undo_placement(&mut self) {
+        self.board.recall(&mut self.players[self.current]);
+    }
+
+    fn end_turn(&mut self, points: u32) {
+        if points == 0 {
+            self.scoreless_turns += 1;
+        } else {
+            self.scoreless_turns = 0;
+        }
+
+        if self.tile_bag.is_empty() && self.players[self.current].tiles.is_empty() {
+            // Otherwise the next player may still challenge the play.
+            if self.challenge_rule == ChallengeRule::Void {
+                self.finish(Some(self.current));
+            } else {
+                self.next_turn();
+            }
+        } else if self.scoreless_turns >= MAX_SCORELESS_TURNS {
+            self.finish(None);
+        } else {
+            self.next_turn();
+        }
+    }
+
+    /// Subtracts every unplayed rack from its owner's score. The player who
+    /// went out, if any, is given the sum of those racks.
+    fn finish(&mut self, out: Option<usize>) {
+        let mut unplayed_total = 0;
+
+        for player in self.players.iter_mut() {
+            let unplayed = player.rack_value();
+            player.score = player.score.saturating_sub(unplayed);
+            unplayed_total += unplayed;
+        }
+
+        if let Some(out) = out {
+            self.players[out].score += unplayed_total;
+        }
+
+        self.finished = true;
+    }
+
+    fn next_turn(&mut self) {
+        self.current = (self.current + 1) % self.players.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::lexicon::Lexicon;
+    use crate::engine::player::{Player, RACK_SIZE};
+    use crate::engine::testing::*;
+
+    #[test]
+    fn plays_score_and_refill() {
+        let mut state = State::new(Some(1));
+        deal(&mut state, 0, "CATEEEE");
+        place(&mut state, 7, 7, "cat");
+        let bag = state.tile_bag.len();
+
+        let played = state.act(Action::Submit);
+        assert!(matches!(played, Ok(Outcome::Played(score)) if score.total == 10));
+        assert_eq!(state.players[0].score, 10);
+        assert_eq!(state.players[0].tiles.len(), RACK_SIZE);
+        assert_eq!(state.tile_bag.len(), bag - 3);
+        assert_eq!(state.current, 1);
+    }
+
+    #[test]
+    fn rejects_invalid_words() {
+        let mut state = State::new(Some(1));
+        deal(&mut state, 0, "QZEEEEE");
+        place(&mut state, 7, 7, "qz");
+
+        assert_eq!(
+            state.act(Action::Submit),
+            Err(MoveError::InvalidWords(vec!["QZ".to_string()]))
+        );
+        assert_eq!(state.current, 0);
+    }
+
+    #[test]
+    fn passing_recalls_placed_tiles() {
+        let mut state = State::new(Some(1));
+        deal(&mut state, 0, "CATEEEE");
+        place(&mut state, 7, 7, "cat");
+
+        assert_eq!(state.act(Action::Pass), Ok(Outcome::Passed));
+        assert_eq!(state.board.get(7, 7), None);
+        assert_eq!(state.players[0].tiles.len(), RACK_SIZE);
+        assert_eq!(state.current, 1);
+    }
+
+    #[test]
+    fn exchanges_return_tiles_to_the_bag() {
+        let mut state = State::new(Some(1));
+        let bag = state.tile_bag.len();
+
+        assert_eq!(
+            state.act(Action::Exchange(vec![0, 1, 1, 9])),
+            Ok(Outcome::Exchanged(2))
+        );
+        assert_eq!(state.tile_bag.len(), bag);
+        assert_eq!(state.players[0].tiles.len(), RACK_SIZE);
+        assert_eq!(state.current, 1);
+
+        state.tile_bag.truncate(MIN_BAG_FOR_EXCHANGE - 1);
+        assert_eq!(
+            state.act(Action::Exchange(vec![0])),
+            Err(MoveError::BagTooSmall)
+        );
+    }
+
+    #[test]
+    fn exchanging_recalls_placed_tiles() {
+        let mut state = State::new(Some(1));
+        deal(&mut state, 0, "CATEEEE");
+        place(&mut state, 7, 7, "cat");
+        let bag = state.tile_bag.len();
+
+        assert_eq!(
+            state.act(Action::Exchange(vec![0])),
+            Ok(Outcome::Exchanged(1))
+        );
+        assert!(state.board.squares().iter().flatten().all(Option::is_none));
+        assert_eq!(state.players[0].tiles.len(), RACK_SIZE);
+        assert_eq!(state.tile_bag.len(), bag);
+    }
+
+    #[test]
+    fn scoreless_turns_end_the_game() {
+        let mut state = State::new(Some(1));
+        for _ in 0..MAX_SCORELESS_TURNS {
+            state.act(Action::Pass).unwrap();
+        }
+
+        assert!(state.finished);
+        assert_eq!(state.act(Action::Pass), Err(MoveError::GameOver));
+    }
+
+    #[test]
+    fn going_out_takes_the_other_rack() {
+        let mut state = State::new(Some(1));
+        deal(&mut state, 0, "CAT");
+        state.tile_bag.clear();
+        let left = state.players[1].rack_value();
+        state.players[1].score = 100;
+        place(&mut state, 7, 7, "cat");
+
+        state.act(Action::Submit).unwrap();
+        assert!(state.finished);
+        assert_eq!(state.players[0].score, 10 + left);
+        assert_eq!(state.players[1].score, 100 - left);
+    }
+
+    /// A game between `seats` players with scores of 100 and an empty
+    /// bag. Player 1 holds `rack`.
+    fn endgame(seats: usize, rack: &str) -> State {
+        let players = (0..seats).map(Player::seated).collect();
+        let mut state = State::with_players(Some(1), Lexicon::sowpods(), players);
+        deal(&mut state, 0, rack);
+        state.tile_bag.clear();
+        for player in state.players.iter_mut() {
+            player.score = 100;
+        }
+        state
+    }
+
+    #[test]
+    fn going_out_takes_every_rack() {
+        let mut state = endgame(3, "CAT");
+        let left: Vec<u32> = state.players.iter().map(|p| p.rack_value()).collect();
+        place(&mut state, 7, 7, "cat");
+        state.act(Action::Submit).unwrap();
+
+        assert!(state.finished);
+        assert_eq!(state.players[0].score, 110 + left[1] + left[2]);
+        assert_eq!(state.players[1].score, 100 - left[1]);
+        assert_eq!(state.players[2].score, 100 - left[2]);
+    }
+
+    #[test]
+    fn scoreless_endings_cost_every_rack() {
+        let mut state = endgame(4, "CATEEEE");
+        let left: Vec<u32> = state.players.iter().map(|p| p.rack_value()).collect();
+        while !state.finished {
+            state.act(Action::Pass).unwrap();
+        }
+
+        assert_eq!(state.scoreless_turns, MAX_SCORELESS_TURNS);
+        for (player, left) in state.players.iter().zip(left) {
+            assert_eq!(player.score, 100 - left);
+        }
+    }
+
+    #[test]
+    fn replays_the_history() {
+        let state = bot_game(12, 10);
+        let turns = state.history.len();
+        assert!(!state.finished);
+
+        assert!(state
+            .board_after(0)
+            .squares()
+            .iter()
+            .flatten()
+            .all(Option::is_none));
+        assert_eq!(state.board_after(turns).squares(), state.board.squares());
+        let scores: Vec<_> = state.players.iter().map(|p| p.score as i32).collect();
+        assert_eq!(state.scores_after(turns), scores);
+    }
+
+    /// Player 1 has played `word` across from the center under `rule`.
+    fn played(rule: ChallengeRule, rack: &str, word: &str) -> State {
+        let mut state = State::new(Some(9));
+        state.challenge_rule = rule;
+        deal(&mut state, 0, rack);
+        place(&mut state, 7, 7, word);
+        state.act(Action::Submit).unwrap();
+        state
+    }
+
+    #[test]
+    fn void_refuses_phonies() {
+        let mut state = State::new(Some(9));
+        deal(&mut state, 0, "QZEEEEE");
+        place(&mut state, 7, 7, "qz");
+
+        assert_eq!(
+            state.act(Action::Submit),
+            Err(MoveError::InvalidWords(vec!["QZ".to_string()]))
+        );
+        assert_eq!(
+            state.act(Action::Challenge),
+            Err(MoveError::NothingToChallenge)
+        );
+    }
+
+    #[test]
+    fn phonies_are_withdrawn() {
+        let before = State::new(Some(9));
+        let bag = before.tile_bag.len();
+        let mut state = played(ChallengeRule::Single, "QZEEEEE", "qz");
+        assert_eq!(state.phonies(), Some(vec!["QZ".to_string()]));
+
+        assert_eq!(
+            state.act(Action::Challenge),
+            Ok(Outcome::Challenged(vec!["QZ".to_string()]))
+        );
+        assert!(state.board.squares().iter().flatten().all(Option::is_none));
+        assert_eq!(state.tile_bag.len(), bag);
+        assert_eq!(state.players[0].score, 0);
+        assert_eq!(state.players[0].tiles.len(), 7);
+        assert_eq!(state.current, 1);
+        assert_eq!(
+            state.history.last().map(|r| (&r.play, r.points)),
+            Some((&Play::Withdrawn, -40))
+        );
+        assert!(state.board_after(1).get(7, 7).is_some());
+        assert!(state.board_after(2).get(7, 7).is_none());
+
+        assert_eq!(
+            state.act(Action::Challenge),
+            Err(MoveError::NothingToChallenge)
+        );
+    }
+
+    #[test]
+    fn failed_challenges() {
+        let mut single = played(ChallengeRule::Single, "ZAEEEEE", "za");
+        assert_eq!(
+            single.act(Action::Challenge),
+            Ok(Outcome::Challenged(vec![]))
+        );
+        assert_eq!(single.current, 1);
+        assert_eq!(
+            single.history.last().map(|r| &r.play),
+            Some(&Play::FailedChallenge { lost_turn: false })
+        );
+
+        let mut double = played(ChallengeRule::Double, "ZAEEEEE", "za");
+        double.act(Action::Challenge).unwrap();
+        assert_eq!(double.current, 0);
+        assert_eq!(
+            double.history.last().map(|r| &r.play),
+            Some(&Play::FailedChallenge { lost_turn: true })
+        );
+
+        let mut five = played(ChallengeRule::FivePoint, "ZAEEEEE", "za");
+        let score = five.players[0].score;
+        five.act(Action::Challenge).unwrap();
+        assert_eq!(five.current, 1);
+        assert_eq!(five.players[0].score, score + CHALLENGE_BONUS as u32);
+        assert_eq!(five.scores_after(five.history.len())[0], score as i32 + 5);
+    }
+
+    #[test]
+    fn only_the_next_player_challenges() {
+        let mut state = played(ChallengeRule::Single, "ZAEEEEE", "za");
+        state.act(Action::Pass).unwrap();
+        assert_eq!(state.phonies(), None);
+        assert_eq!(
+            state.act(Action::Challenge),
+            Err(MoveError::NothingToChallenge)
+        );
+    }
+
+    /// Player 1 goes out with `word` under the double challenge rule.
+    fn went_out(word: &str) -> State {
+        let mut state = State::new(Some(9));
+        state.challenge_rule = ChallengeRule::Double;
+        deal(&mut state, 0, &word.to_uppercase());
+        state.tile_bag.clear();
+        place(&mut state, 7, 7, word);
+        state.act(Action::Submit).unwrap();
+        state
+    }
+
+    #[test]
+    fn phonies_that_go_out_can_be_challenged() {
+        let mut state = went_out("qz");
+        assert!(!state.finished);
+        assert_eq!(state.going_out(), Some(0));
+        assert_eq!(state.act(Action::Pass), Err(MoveError::ChallengeOrAccept));
+
+        state.act(Action::Challenge).unwrap();
+        assert!(!state.finished);
+        assert_eq!(state.players[0].tiles.len(), 2);
+        assert_eq!(state.players[0].score, 0);
+    }
+
+    #[test]
+    fn valid_plays_that_go_out_end_the_game() {
+        let rack = |state: &State| state.players[1].rack_value() as i32;
+
+        let mut accepted = went_out("za");
+        let left = rack(&accepted);
+        assert_eq!(accepted.act(Action::Accept), Ok(Outcome::Accepted));
+        assert!(accepted.finished);
+        assert_eq!(accepted.players[0].score as i32, 22 + left);
+
+        let mut challenged = went_out("za");
+        assert_eq!(
+            challenged.act(Action::Challenge),
+            Ok(Outcome::Challenged(vec![]))
+        );
+        assert!(challenged.finished);
+        assert_eq!(challenged.players[0].score as i32, 22 + left);
+        assert_eq!(challenged.act(Action::Accept), Err(MoveError::GameOver));
+    }
+}
@@ -1,7 +1,8 @@
 use macroquad::color_u8;
 use macroquad::prelude::*;
 
-use super::player::*;
+use scrabble::engine::*;
+
 use super::tile::*;
 
 use crate::game::BACKGROUND;
@@ -12,78 +13,6 @@ pub const BLUE: Color = color_u8!(44, 150, 181, 255);
 pub const PINK: Color = color_u8!(237, 177, 167, 255);
 pub const TAN: Color = color_u8!(198, 192, 168, 255);
 
-const TRIPLE_WORD: &[(u8, u8)] = &[
-    (0, 0),
-    (0, 7),
-    (0, 14),
-    (7, 0),
-    (7, 14),
-    (14, 0),
-    (14, 7),
-    (14, 14),
-];
-
-const DOUBLE_LETTER: &[(u8, u8)] = &[
-    (0, 3),
-    (0, 11),
-    (3, 0),
-    (3, 14),
-    (11, 0),
-    (11, 14),
-    (14, 3),
-    (14, 11),
-    (2, 6),
-    (3, 7),
-    (2, 8),
-    (12, 6),
-    (11, 7),
-    (12, 8),
-    (6, 2),
-    (7, 3),
-    (8, 2),
-    (6, 12),
-    (7, 11),
-    (8, 12),
-    (8, 8),
-    (6, 6),
-    (8, 6),
-    (6, 8),
-];
-
-const TRIPLE_LETTER: &[(u8, u8)] = &[
-    (1, 5),
-    (1, 9),
-    (5, 5),
-    (5, 9),
-    (9, 5),
-    (9, 9),
-    (13, 5),
-    (13, 9),
-    (5, 1),
-    (9, 1),
-    (5, 13),
-    (9, 13),
-];
-
-const DOUBLE_WORD: &[(u8, u8)] = &[
-    (1, 1),
-    (1, 13),
-    (13, 1),
-    (13, 13),
-    (2, 2),
-    (2, 12),
-    (12, 2),
-    (12, 12),
-    (11, 3),
-    (11, 11),
-    (3, 3),
-    (3, 11),
-    (10, 4),
-    (10, 10),
-    (4, 4),
-    (4, 10),
-];
-
 pub struct Consts {
     pub step: f32,
     pub letter_size: f32,
@@ -94,6 +23,18 @@ pub struct Consts {
     pub board_lower: (f32, f32),
     pub rack_upper: (f32, f32),
     pub rack_lower: (f32, f32),
+    /// Text size of the scoreboard above the board.
+    pub hud_size: f32,
+    /// Baseline of the first scoreboard line, and the gap to the next.
+    pub hud_top: f32,
+    pub hud_line: f32,
+    /// Left edge and width of the history right of the board, which takes
+    /// two lines per turn.
+    pub history_left: f32,
+    pub history_width: f32,
+    pub history_size: f32,
+    pub history_top: f32,
+    pub history_line: f32,
 }
 
 impl Consts {
@@ -125,6 +66,16 @@ impl Consts {
         let rack_upper = (rack_upper_x, rack_upper_y);
         let rack_lower = (rack_lower_x, rack_lower_y);
 
+        let hud_size = letter_size / 2.0;
+        let hud_line = hud_size * 1.5;
+        let hud_top = screen_height() / 2.0 - step * 7.0 - offset / 4.0 - letter_size - hud_line;
+
+        let history_left = screen_width() / 2.0 + step * 8.25;
+        let history_width = screen_width() - history_left - step / 4.0;
+        let history_size = letter_size / 2.5;
+        let history_top = screen_height() / 2.0 - step * 7.0 + history_size;
+        let history_line = history_size * 1.2;
+
         Consts {
             offset,
             step,
@@ -135,6 +86,14 @@ impl Consts {
             board_lower,
             rack_upper,
             rack_lower,
+            hud_size,
+            hud_top,
+            hud_line,
+            history_left,
+            history_width,
+            history_size,
+            history_top,
+            history_line,
         }
     }
 
@@ -165,6 +124,22 @@ impl Consts {
         let rack_upper_y = rack_lower_y + self.step;
         self.rack_upper = (rack_upper_x, rack_upper_y);
         self.rack_lower = (rack_lower_x, rack_lower_y);
+
+        // Two lines above the message, which sits above the board.
+        self.hud_size = self.letter_size / 2.0;
+        self.hud_line = self.hud_size * 1.5;
+        self.hud_top = screen_height() / 2.0
+            - self.step * 7.0
+            - self.offset / 4.0
+            - self.letter_size
+            - self.hud_line;
+
+        // Between the board and the right edge of the window.
+        self.history_left = screen_width() / 2.0 + self.step * 8.25;
+        self.history_width = screen_width() - self.history_left - self.step / 4.0;
+        self.history_size = self.letter_size / 2.5;
+        self.history_top = screen_height() / 2.0 - self.step * 7.0 + self.history_size;
+        self.history_line = self.history_size * 1.2;
     }
 }
 
@@ -173,31 +148,47 @@ pub enum SelectedTile {
     None,
     Board(usize, usize),
     Rack(usize),
+    /// Rack tiles marked for an exchange.
+    Exchange([bool; RACK_SIZE]),
 }
 
-pub struct Board {
-    board: [[Option<Tile>; 15]; 15],
+pub struct BoardView {
     pub selected_tile: SelectedTile,
     pub consts: Consts,
 }
 
-impl Board {
-    pub fn new() -> Board {
-        Board {
-            board: [[None; 15]; 15],
+impl BoardView {
+    pub fn new() -> BoardView {
+        BoardView {
             selected_tile: SelectedTile::None,
             consts: Consts::new(),
         }
     }
 
-    pub fn draw(&self, player: &Player) {
+    pub fn draw(&self, board: &Board, player: &Player, message: Option<&str>) {
         clear_background(BACKGROUND);
-        self.draw_tiles();
+        self.draw_tiles(board);
         self.draw_rack(player);
+
+        if let Some(message) = message {
+            self.draw_message(message);
+        }
+    }
+
+    pub fn draw_message(&self, message: &str) {
+        let size = self.consts.letter_size / 1.5;
+        let text_size = measure_text(message, None, size as u16, 1.0);
+        draw_text(
+            message,
+            (screen_width() - text_size.width) / 2.0,
+            screen_height() / 2.0 - self.consts.step * 7.0 - self.consts.offset / 4.0,
+            size,
+            TAN,
+        );
     }
 
-    pub fn draw_tiles(&self) {
-        for (i, row) in self.board.iter().enumerate() {
+    pub fn draw_tiles(&self, board: &Board) {
+        for (i, row) in board.squares().iter().enumerate() {
             for (o, tile) in row.iter().enumerate() {
                 let x = o as f32 * self.consts.step + screen_width() / 2.0 - self.consts.step * 7.0;
                 let y =
@@ -205,20 +196,27 @@ impl Board {
 
                 let i = i as u8;
                 let o = o as u8;
-                let tile = tile.unwrap_or(Tile::Blank);
 
-                if i == 7 && o == 7 {
-                    tile.draw(x, y, TAN, &self.consts);
+                let background = if i == 7 && o == 7 {
+                    Some(TAN)
                 } else if TRIPLE_WORD.contains(&(i, o)) {
-                    tile.draw(x, y, RED, &self.consts);
+                    Some(RED)
                 } else if DOUBLE_LETTER.contains(&(i, o)) {
-                    tile.draw(x, y, LIGHT_BLUE, &self.consts);
+                    Some(LIGHT_BLUE)
                 } else if TRIPLE_LETTER.contains(&(i, o)) {
-                    tile.draw(x, y, BLUE, &self.consts);
+                    Some(BLUE)
                 } else if DOUBLE_WORD.contains(&(i, o)) {
-                    tile.draw(x, y, PINK, &self.consts);
-                } else if let Some(tile) = self.board[i as usize][o as usize] {
-                    tile.draw(x, y, TAN, &self.consts);
+                    Some(PINK)
+                } else {
+                    tile.map(|_| TAN)
+                };
+
+                match (tile, background) {
+                    (Some(tile), Some(background)) => tile.draw(x, y, background, &self.consts),
+                    (None, Some(background)) => {
+                        draw_rectangle(x, y, self.consts.step, self.consts.step, background)
+                    }
+                    _ => {}
                 }
 
                 if self.selected_tile == SelectedTile::Board(i as usize, o as usize) {
@@ -248,33 +246,155 @@ impl Board {
             let y = screen_height() / 2.0 + self.consts.step * 7.0 + self.consts.offset / 2.0;
             tile.draw(x, y, TAN, &self.consts);
 
-            if let SelectedTile::Rack(selected_tile) = self.selected_tile {
-                if selected_tile == i as usize {
-                    let x = x - self.consts.selected_tile_glow_thickness;
-                    let y = y - self.consts.selected_tile_glow_thickness;
-                    let w = self.consts.step + 2.0 * self.consts.selected_tile_glow_thickness;
-                    let h = self.consts.step + 2.0 * self.consts.selected_tile_glow_thickness;
-                    draw_rectangle_lines(
-                        x,
-                        y,
-                        w,
-                        h,
-                        self.consts.selected_tile_glow_thickness,
-                        GOLD,
-                    );
-                }
+            let selected = match self.selected_tile {
+                SelectedTile::Rack(selected_tile) => selected_tile == i as usize,
+                SelectedTile::Exchange(selected) => selected[i as usize],
+                _ => false,
+            };
+
+            if selected {
+                let x = x - self.consts.selected_tile_glow_thickness;
+                let y = y - self.consts.selected_tile_glow_thickness;
+                let w = self.consts.step + 2.0 * self.consts.selected_tile_glow_thickness;
+                let h = self.consts.step + 2.0 * self.consts.selected_tile_glow_thickness;
+                draw_rectangle_lines(x, y, w, h, self.consts.selected_tile_glow_thickness, GOLD);
             }
         }
     }
 
-    pub fn get_board_tile(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+    /// Draws `tiles` over the board, outlined like a selected tile, without
+    /// placing them.
+    pub fn draw_preview(&self, tiles: &[(usize, usize, PlacedTile)]) {
+        for &(row, col, tile) in tiles {
+            let x = col as f32 * self.consts.step + screen_width() / 2.0 - self.consts.step * 7.0;
+            let y = row as f32 * self.consts.step + screen_height() / 2.0 - self.consts.step * 7.0;
+
+            tile.draw(x, y, TAN, &self.consts);
+            draw_rectangle_lines(
+                x,
+                y,
+                self.consts.step,
+                self.consts.step,
+                self.consts.selected_tile_glow_thickness,
+                GOLD,
+            );
+        }
+    }
+
+    /// Lists `hints` left of the board with the `selected` one in gold.
+    pub fn draw_hints(&self, hints: &[Candidate], selected: usize) {
+        let size = self.consts.letter_size / 2.0;
+        let x = self.consts.step / 2.0;
+        let top = screen_height() / 2.0 - self.consts.step * 7.0 + size;
+
+        for (i, hint) in hints.iter().enumerate() {
+            let text = format!(
+                "{}. {} {}",
+                i + 1,
+                hint.words()[0].to_uppercase(),
+                hint.score.total
+            );
+            let color = if i == selected { GOLD } else { TAN };
+            draw_text(&text, x, top + i as f32 * size * 1.5, size, color);
+        }
+    }
+
+    /// Shows every player's score with the one to move in gold, then the
+    /// tiles left in the bag, the last move and the spread of the player
+    /// to move over the best of the others.
+    pub fn draw_hud(&self, state: &State) {
+        let size = self.consts.hud_size;
+        let gap = measure_text("   ", None, size as u16, 1.0).width;
+
+        let scores: Vec<(String, Color)> = state
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| {
+                let color = if i == state.current && !state.finished {
+                    GOLD
+                } else {
+                    TAN
+                };
+                (format!("{} {}", player.name, player.score), color)
+            })
+            .collect();
+        let widths: Vec<f32> = scores
+            .iter()
+            .map(|(text, _)| measure_text(text, None, size as u16, 1.0).width)
+            .collect();
+        let total = widths.iter().sum::<f32>() + gap * (widths.len() - 1) as f32;
+
+        let mut x = (screen_width() - total) / 2.0;
+        for ((text, color), width) in scores.iter().zip(widths) {
+            draw_text(text, x, self.consts.hud_top, size, *color);
+            x += width + gap;
+        }
+
+        let mut status = vec![format!("Bag {}", state.tile_bag.len())];
+        if let Some(record) = state.history.last() {
+            status.push(format!(
+                "Last: {} {} {:+}",
+                state.players[record.player].name, record.play, record.points
+            ));
+        }
+        status.push(format!("Spread {:+}", state.spread(state.current)));
+
+        let status = status.join("   ");
+        let width = measure_text(&status, None, size as u16, 1.0).width;
+        draw_text(
+            &status,
+            (screen_width() - width) / 2.0,
+            self.consts.hud_top + self.consts.hud_line,
+            size,
+            TAN,
+        );
+    }
+
+    /// Lists the turns in `history` right of the board, each as the player's
+    /// name over the play, keeping the `selected` one in view and in gold.
+    pub fn draw_history(&self, players: &[Player], history: &[Record], selected: Option<usize>) {
+        let size = self.consts.history_size;
+        let line = self.consts.history_line;
+        let x = self.consts.history_left;
+        let indent = measure_text("  ", None, size as u16, 1.0).width;
+
+        let rows = (self.consts.step * 15.0 / (line * 2.0)) as usize;
+        let first = match selected {
+            Some(selected) => selected.saturating_sub(rows / 2),
+            None => history.len(),
+        }
+        .min(history.len().saturating_sub(rows));
+
+        for (i, record) in history.iter().enumerate().skip(first).take(rows) {
+            let name = players
+                .get(record.player)
+                .map_or("?", |player| player.name.as_str());
+            let who = format!("{}. {}", i + 1, name);
+            let what = format!("{} {}", record.play, record.points);
+            let color = if selected == Some(i) { GOLD } else { TAN };
+            let y = self.consts.history_top + (i - first) as f32 * line * 2.0;
+
+            let width = self.consts.history_width;
+            draw_text(&fit(&who, size, width), x, y, size, color);
+            draw_text(
+                &fit(&what, size, width - indent),
+                x + indent,
+                y + line,
+                size,
+                color,
+            );
+        }
+    }
+
+    pub fn get_board_tile(&self, x: f32, y: f32, board: &Board) -> Option<(usize, usize)> {
         if (self.consts.board_lower.0..=self.consts.board_upper.0).contains(&x)
             && (self.consts.board_lower.1..=self.consts.board_upper.1).contains(&y)
         {
             let row = ((y - self.consts.board_lower.1) / self.consts.step) as usize;
             let col = ((x - self.consts.board_lower.0) / self.consts.step) as usize;
 
-            if let Some(_tile) = self.board[row][col] {
+            if board.get(row, col).is_some() {
                 return Some((row, col));
             }
         }
@@ -303,7 +423,7 @@ impl Board {
         None
     }
 
-    pub fn place_tile(&mut self, x: f32, y: f32, player: &mut Player) {
+    pub fn place_tile(&mut self, x: f32, y: f32, state: &mut State) {
         if let SelectedTile::None = self.selected_tile {
             return;
         }
@@ -312,17 +432,17 @@ impl Board {
             && (self.consts.board_lower.1..=self.consts.board_upper.1).contains(&y)
         {
             if let SelectedTile::Rack(selected_tile) = self.selected_tile {
-                let tile = player.tiles.remove(selected_tile);
-                let row = ((y - self.consts.board_lower.1) / self.consts.step as f32) as usize;
-                let col = ((x - self.consts.board_lower.0) / self.consts.step as f32) as usize;
+                let row = ((y - self.consts.board_lower.1) / self.consts.step) as usize;
+                let col = ((x - self.consts.board_lower.0) / self.consts.step) as usize;
 
-                self.board[row][col] = Some(tile);
-                self.selected_tile = SelectedTile::None;
+                if state.place_tile(selected_tile, row, col) {
+                    self.selected_tile = SelectedTile::None;
+                }
             }
         }
     }
 
-    pub fn remove_tile_from_board(&mut self, x: f32, y: f32, player: &mut Player) {
+    pub fn remove_tile_from_board(&mut self, x: f32, y: f32, state: &mut State) {
         if let SelectedTile::None = self.selected_tile {
             return;
         }
@@ -330,11 +450,10 @@ impl Board {
         if (self.consts.board_lower.0..=self.consts.board_upper.0).contains(&x)
             && (self.consts.board_lower.1..=self.consts.board_upper.1).contains(&y)
         {
-            let row = ((y - self.consts.board_lower.1) / self.consts.step as f32) as usize;
-            let col = ((x - self.consts.board_lower.0) / self.consts.step as f32) as usize;
-            if self.board[row][col].is_some() {
-                player.tiles.push(self.board[row][col].unwrap());
-                self.board[row][col] = None;
+            let row = ((y - self.consts.board_lower.1) / self.consts.step) as usize;
+            let col = ((x - self.consts.board_lower.0) / self.consts.step) as usize;
+            if state.board.is_placed(row, col) {
+                state.take_back(row, col);
 
                 if SelectedTile::Board(row, col) == self.selected_tile {
                     self.selected_tile = SelectedTile::None;
@@ -343,7 +462,7 @@ impl Board {
         }
     }
 
-    pub fn swap_tile_on_board(&mut self, x: f32, y: f32) {
+    pub fn swap_tile_on_board(&mut self, x: f32, y: f32, board: &mut Board) {
         if let SelectedTile::None = self.selected_tile {
             return;
         }
@@ -351,13 +470,10 @@ impl Board {
         if (self.consts.board_lower.0..=self.consts.board_upper.0).contains(&x)
             && (self.consts.board_lower.1..=self.consts.board_upper.1).contains(&y)
         {
-            let row = ((y - self.consts.board_lower.1) / self.consts.step as f32) as usize;
-            let col = ((x - self.consts.board_lower.0) / self.consts.step as f32) as usize;
-            if let Some(tile) = self.board[row][col] {
-                if let SelectedTile::Board(selected_row, selected_col) = self.selected_tile {
-                    let tmp = self.board[selected_row][selected_col];
-                    self.board[selected_row][selected_col] = Some(tile);
-                    self.board[row][col] = tmp;
+            let row = ((y - self.consts.board_lower.1) / self.consts.step) as usize;
+            let col = ((x - self.consts.board_lower.0) / self.consts.step) as usize;
+            if let SelectedTile::Board(selected_row, selected_col) = self.selected_tile {
+                if board.swap_placed((selected_row, selected_col), (row, col)) {
                     self.selected_tile = SelectedTile::Board(row, col);
                 }
             }
@@ -395,3 +511,19 @@ impl Board {
         }
     }
 }
+
+/// `text` shortened to fit in `width` at `size`, ending in `..` when cut.
+fn fit(text: &str, size: f32, width: f32) -> String {
+    let fits = |text: &str| measure_text(text, None, size as u16, 1.0).width <= width;
+    if fits(text) {
+        return text.to_string();
+    }
+
+    let mut text = text.to_string();
+    while text.pop().is_some() {
+        if fits(&format!("{}..", text)) {
+            return format!("{}..", text);
+        }
+    }
+    String::new()
+}
@@ -1,12 +1,8 @@
-use ::rand;
 use macroquad::prelude::*;
-use rust_embed::RustEmbed;
-use std::collections::HashSet;
-use std::io::{BufRead, BufReader};
-
-#[derive(RustEmbed)]
-#[folder = "assets/"]
-struct Asset;
+use scrabble::engine::*;
+use std::path::PathBuf;
+use std::process::exit;
+use std::thread::{self, JoinHandle};
 
 mod tile;
 use tile::*;
@@ -14,146 +10,920 @@ use tile::*;
 mod board;
 use board::*;
 
-mod player;
-use player::*;
-
 mod screens;
 use screens::*;
 
+/// Seconds a computer move stays on the board before it is submitted.
+const BOT_DELAY: f64 = 1.0;
+/// How many moves a hint lists.
+const HINT_COUNT: usize = 5;
+/// Longest name that can be typed on the setup screen.
+const MAX_NAME_LEN: usize = 12;
+/// What a seat can be switched between on the setup screen, in order.
+const CONTROLLERS: [Controller; 4] = [
+    Controller::Human,
+    Controller::Computer(Level::Beginner),
+    Controller::Computer(Level::Intermediate),
+    Controller::Computer(Level::Expert),
+];
+
 pub struct Game {
-    pub tile_bag: Vec<Tile>,
-    pub score: u32,
-    pub words: HashSet<String>,
-    pub board: Board,
-    pub players: [Player; 2],
+    pub state: State,
+    pub view: BoardView,
     pub screen: Screen,
+    pub message: Option<String>,
+    /// Difficulty for computer players picked on the start screen.
+    pub level: Level,
+    /// Whether committed turns can be undone, and not only tiles placed
+    /// this turn.
+    pub casual: bool,
+    /// The best moves for the current rack while `Screen::Hint` is shown.
+    pub hints: Vec<Candidate>,
+    /// The names and controllers picked on the setup screen.
+    pub seats: Vec<Player>,
+    /// Where the game is saved, and autosaved on exit.
+    pub save_path: PathBuf,
+    /// Whether `save_path` holds this game, loaded from or saved to it.
+    /// Only then is the save removed once the game is over.
+    saved: bool,
+    /// Where `G` exports the game as GCG and `I` imports one from.
+    pub gcg_path: PathBuf,
+    leaves: LeaveTable,
+    bots: Vec<Option<Bot>>,
+    bot_placed_at: Option<f64>,
+    simulation: Option<Simulation>,
+    endgame: Option<JoinHandle<Option<EndgameSolution>>>,
+    /// The computer deciding its move in the background, which can take a
+    /// while once it searches the endgame.
+    thinking: Option<JoinHandle<(Bot, Decision)>>,
+    /// States to go back to with Ctrl+Z, newest last.
+    undo: Vec<State>,
+    /// States undone, to go forward to with Ctrl+Y.
+    redo: Vec<State>,
+    /// The seat whose rack is shown in a hot-seat game.
+    revealed: Option<usize>,
 }
 
 impl Game {
-    pub fn new() -> Game {
+    pub fn new(
+        seed: Option<u64>,
+        leaves: LeaveTable,
+        save_path: PathBuf,
+        gcg_path: PathBuf,
+    ) -> Game {
         let screen = Screen::Start;
-        Screen::draw_start();
+        Screen::draw_start(
+            Level::default(),
+            ChallengeRule::default(),
+            true,
+            save_path.exists(),
+        );
+        prevent_quit();
+
+        Game {
+            state: State::new(seed),
+            view: BoardView::new(),
+            screen,
+            message: None,
+            level: Level::default(),
+            casual: true,
+            hints: vec![],
+            seats: (0..MIN_PLAYERS).map(Player::seated).collect(),
+            save_path,
+            saved: false,
+            gcg_path,
+            leaves,
+            bots: vec![],
+            bot_placed_at: None,
+            simulation: None,
+            endgame: None,
+            thinking: None,
+            undo: vec![],
+            redo: vec![],
+            revealed: None,
+        }
+    }
 
-        let mut tile_bag = vec![];
-        let mut rng = rand::thread_rng();
+    pub fn play(&mut self) {
+        if is_quit_requested() {
+            self.screen = Screen::Quit;
+        }
 
-        for i in Tile::iter() {
-            let quantity = i.get_quantity();
-            for _ in 0..quantity {
-                tile_bag.push(*i);
-            }
+        self.check_handover();
+        self.screen = self.screen.draw(self);
+        if let Screen::Quit = self.screen {
+            self.quit();
         }
+        self.view.consts.update(self.state.player());
 
-        let content: Vec<_> = Asset::get("sowpods.txt").unwrap().data.into();
-        let content = content.as_slice();
+        self.poll_simulation();
+        self.poll_endgame();
+        self.handle_movement();
+    }
 
-        let word_file = BufReader::new(content);
-        let mut words = HashSet::new();
-        for word in word_file.lines() {
-            words.insert(word.unwrap());
+    fn handle_movement(&mut self) {
+        if let Screen::Start = self.screen {
+            self.handle_start();
+            return;
         }
 
-        let mut players = [Player::new(), Player::new()];
+        if let Screen::Setup(selected) = self.screen {
+            self.handle_setup(selected);
+            return;
+        }
 
-        for player in players.iter_mut() {
-            player.fill_tiles(&mut tile_bag, &mut rng);
+        if let Screen::Handover = self.screen {
+            if get_last_key_pressed().is_some() {
+                self.revealed = Some(self.state.current);
+                self.screen = Screen::Main;
+            }
+            return;
         }
 
-        Game {
-            tile_bag,
-            score: 0,
-            words,
-            board: Board::new(),
-            players,
-            screen,
+        if let Screen::PickBlank(row, col) = self.screen {
+            self.pick_blank(row, col);
+            return;
         }
-    }
 
-    pub fn play(&mut self) {
-        self.screen = self.screen.draw(self);
-        self.board.consts.update(&self.players[0]);
+        if let Screen::Hint(selected) = self.screen {
+            self.handle_hint(selected);
+            return;
+        }
 
-        self.handle_movement();
-    }
+        if let Screen::Replay(turns) = self.screen {
+            self.handle_replay(turns);
+            return;
+        }
+
+        let control = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if control && is_key_pressed(KeyCode::Z) {
+            self.undo();
+            return;
+        } else if control && is_key_pressed(KeyCode::Y) {
+            self.redo();
+            return;
+        }
+
+        if let Screen::GameOver = self.screen {
+            if is_key_pressed(KeyCode::R) {
+                self.screen = Screen::Replay(self.state.history.len());
+            }
+            return;
+        }
 
-    fn handle_movement(&mut self) {
         if is_key_pressed(KeyCode::Escape) {
             self.screen = Screen::Exit;
         }
 
+        if self.state.player().is_computer() {
+            self.play_computer();
+            return;
+        }
+
+        if let SelectedTile::Exchange(selected) = self.view.selected_tile {
+            self.handle_exchange(selected);
+            return;
+        }
+
+        if is_key_pressed(KeyCode::Enter) {
+            if self.state.going_out().is_some() {
+                self.undoable(|game| game.act(Action::Accept));
+            } else {
+                self.undoable(|game| game.act(Action::Submit));
+            }
+        } else if is_key_pressed(KeyCode::P) {
+            self.undoable(|game| game.act(Action::Pass));
+        } else if is_key_pressed(KeyCode::C) {
+            self.undoable(|game| game.act(Action::Challenge));
+        } else if is_key_pressed(KeyCode::X) {
+            self.undoable(|game| game.act(Action::UndoPlacement));
+            self.view.selected_tile = SelectedTile::Exchange([false; RACK_SIZE]);
+            self.message =
+                Some("Select tiles to exchange and press Enter. Press X to cancel.".to_string());
+        } else if is_key_pressed(KeyCode::Backspace) {
+            self.undoable(|game| game.act(Action::UndoPlacement));
+        } else if is_key_pressed(KeyCode::A) {
+            self.analyse();
+        } else if is_key_pressed(KeyCode::H) {
+            self.undoable(Game::show_hints);
+        } else if is_key_pressed(KeyCode::R) {
+            self.screen = Screen::Replay(self.state.history.len());
+        } else if is_key_pressed(KeyCode::S) {
+            self.save();
+        } else if is_key_pressed(KeyCode::O) {
+            self.load();
+        } else if is_key_pressed(KeyCode::G) {
+            self.export_gcg();
+        } else if is_key_pressed(KeyCode::I) {
+            self.import_gcg();
+        }
+
         if is_mouse_button_pressed(MouseButton::Left) {
-            self.select_tile_from_rack();
-            self.select_tile_from_board();
-            self.place_tile();
-            self.swap_tile_on_board();
-            self.swap_tile_on_rack();
+            self.undoable(|game| {
+                game.select_tile_from_rack();
+                game.select_tile_from_board();
+                game.place_tile();
+                game.swap_tile_on_board();
+                game.swap_tile_on_rack();
+            });
         } else if is_mouse_button_pressed(MouseButton::Right) {
-            self.select_tile_from_board();
-            self.remove_tile_from_board();
+            self.undoable(|game| {
+                game.select_tile_from_board();
+                game.remove_tile_from_board();
+            });
+        }
+    }
+
+    fn handle_start(&mut self) {
+        if is_key_pressed(KeyCode::L) {
+            self.level = self.level.next();
+            return;
+        }
+
+        if is_key_pressed(KeyCode::C) {
+            self.casual = !self.casual;
+            return;
+        }
+
+        if is_key_pressed(KeyCode::V) {
+            self.state.challenge_rule = self.state.challenge_rule.next();
+            return;
+        }
+
+        if is_key_pressed(KeyCode::Key4) && self.save_path.exists() {
+            self.load();
+            return;
+        }
+
+        if is_key_pressed(KeyCode::N) {
+            // Letters typed so far would otherwise end up in a name.
+            while get_char_pressed().is_some() {}
+            self.screen = Screen::Setup(0);
+            return;
+        }
+
+        let computer = if is_key_pressed(KeyCode::Key1) {
+            None
+        } else if is_key_pressed(KeyCode::Key2) {
+            Some(1)
+        } else if is_key_pressed(KeyCode::Key3) {
+            Some(0)
+        } else {
+            return;
+        };
+
+        if let Some(seat) = computer {
+            self.state.players[seat].controller = Controller::Computer(self.level);
+        }
+
+        self.setup_bots();
+        self.screen = Screen::Main;
+    }
+
+    /// Edits the seat at `selected`: typing changes its name and Left/Right
+    /// switch between a human and the computer levels.
+    fn handle_setup(&mut self, selected: usize) {
+        if is_key_pressed(KeyCode::Escape) {
+            self.screen = Screen::Start;
+        } else if is_key_pressed(KeyCode::Enter) {
+            self.start_setup();
+        } else if is_key_pressed(KeyCode::Up) {
+            self.screen = Screen::Setup(selected.saturating_sub(1));
+        } else if is_key_pressed(KeyCode::Down) {
+            self.screen = Screen::Setup((selected + 1).min(self.seats.len() - 1));
+        } else if is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::Right) {
+            let seat = &mut self.seats[selected];
+            let i = CONTROLLERS
+                .iter()
+                .position(|&c| c == seat.controller)
+                .unwrap_or(0);
+            let step = if is_key_pressed(KeyCode::Left) {
+                CONTROLLERS.len() - 1
+            } else {
+                1
+            };
+            seat.controller = CONTROLLERS[(i + step) % CONTROLLERS.len()];
+        } else if is_key_pressed(KeyCode::Tab) && self.seats.len() < MAX_PLAYERS {
+            self.seats.push(Player::seated(self.seats.len()));
+            self.screen = Screen::Setup(self.seats.len() - 1);
+        } else if is_key_pressed(KeyCode::Delete) && self.seats.len() > MIN_PLAYERS {
+            self.seats.remove(selected);
+            self.screen = Screen::Setup(selected.min(self.seats.len() - 1));
+        } else if is_key_pressed(KeyCode::Backspace) {
+            self.seats[selected].name.pop();
+        }
+
+        let mut typed = vec![];
+        while let Some(c) = get_char_pressed() {
+            typed.push(c);
+        }
+        // The queue is popped newest first.
+        for c in typed.into_iter().rev() {
+            let seat = match self.screen {
+                Screen::Setup(selected) => &mut self.seats[selected],
+                _ => break,
+            };
+            if (c.is_alphanumeric() || c == ' ') && seat.name.chars().count() < MAX_NAME_LEN {
+                seat.name.push(c);
+            }
+        }
+    }
+
+    /// Starts a game between the seats from the setup screen, keeping the
+    /// seed and the challenge rule.
+    fn start_setup(&mut self) {
+        let players = self
+            .seats
+            .iter()
+            .enumerate()
+            .map(|(i, seat)| {
+                let mut player = seat.clone();
+                player.name = seat.name.trim().to_string();
+                if player.name.is_empty() {
+                    player.name = Player::seated(i).name;
+                }
+                player
+            })
+            .collect();
+
+        let rule = self.state.challenge_rule;
+        self.state = State::with_players(Some(self.state.seed), self.state.words.clone(), players);
+        self.state.challenge_rule = rule;
+        self.saved = false;
+        self.setup_bots();
+        self.screen = Screen::Main;
+    }
+
+    fn setup_bots(&mut self) {
+        self.bots = self
+            .state
+            .players
+            .iter()
+            .enumerate()
+            .map(|(seat, player)| match player.controller {
+                Controller::Computer(level) => {
+                    let mut bot = Bot::new(level, self.state.seed.wrapping_add(seat as u64));
+                    bot.leaves = self.leaves.clone();
+                    Some(bot)
+                }
+                Controller::Human => None,
+            })
+            .collect();
+    }
+
+    fn save(&mut self) {
+        self.message = Some(match self.state.save(&self.save_path) {
+            Ok(()) => {
+                self.saved = true;
+                format!("Saved to {}.", self.save_path.display())
+            }
+            Err(e) => format!("Could not save: {}", e),
+        });
+    }
+
+    fn load(&mut self) {
+        let state = match State::load(&self.save_path) {
+            Ok(state) => state,
+            Err(e) => {
+                self.message = Some(format!("Could not load: {}", e));
+                return;
+            }
+        };
+
+        self.replace_state(state);
+        self.saved = true;
+        self.message = Some(format!("Loaded {}.", self.save_path.display()));
+    }
+
+    fn export_gcg(&mut self) {
+        self.message = Some(match self.state.export_gcg(&self.gcg_path) {
+            Ok(()) => format!("Exported to {}.", self.gcg_path.display()),
+            Err(e) => format!("Could not export: {}", e),
+        });
+    }
+
+    /// Opens a GCG game for review.
+    fn import_gcg(&mut self) {
+        let state = match State::import_gcg(&self.gcg_path) {
+            Ok(state) => state,
+            Err(e) => {
+                self.message = Some(format!("Could not import: {}", e));
+                return;
+            }
+        };
+
+        self.replace_state(state);
+        // Finished games stay on the board so they can be reviewed.
+        self.screen = Screen::Main;
+        self.message = Some(format!("Imported {}.", self.gcg_path.display()));
+    }
+
+    fn replace_state(&mut self, state: State) {
+        self.state = state;
+        self.saved = false;
+        self.revealed = None;
+        self.setup_bots();
+        self.undo.clear();
+        self.redo.clear();
+        self.refresh();
+    }
+
+    /// Whether several people share the screen, so racks are hidden
+    /// between turns.
+    pub fn hot_seat(&self) -> bool {
+        self.state
+            .players
+            .iter()
+            .filter(|player| !player.is_computer())
+            .count()
+            > 1
+    }
+
+    /// The rack shown under the board: the current player's, or while a
+    /// computer is to move, the only person's. Nobody's is shown when
+    /// several people share the screen.
+    pub fn shown_rack(&self) -> Option<&Player> {
+        if !self.state.player().is_computer() {
+            return Some(self.state.player());
+        }
+
+        let mut people = self.state.players.iter().filter(|p| !p.is_computer());
+        match (people.next(), people.next()) {
+            (Some(person), None) => Some(person),
+            _ => None,
+        }
+    }
+
+    /// Hides the rack behind `Screen::Handover` when a hot-seat turn passes
+    /// to another person, until they press a key.
+    fn check_handover(&mut self) {
+        if let Screen::Main = self.screen {
+            if self.hot_seat()
+                && !self.state.player().is_computer()
+                && self.revealed != Some(self.state.current)
+            {
+                self.screen = Screen::Handover;
+            }
+        }
+    }
+
+    /// Drops anything worked out for the previous state.
+    fn refresh(&mut self) {
+        self.bot_placed_at = None;
+        self.thinking = None;
+        self.simulation = None;
+        self.endgame = None;
+        self.view.selected_tile = SelectedTile::None;
+        self.screen = if self.state.finished {
+            Screen::GameOver
+        } else {
+            Screen::Main
+        };
+    }
+
+    /// Saves an unfinished game so it can be resumed, then exits. A save of
+    /// this game is removed once it is over, so it is not offered again.
+    fn quit(&mut self) {
+        if self.state.finished && self.saved {
+            if let Err(e) = std::fs::remove_file(&self.save_path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    eprintln!("Could not remove the save: {}", e);
+                }
+            }
+        } else if !self.state.history.is_empty() {
+            if let Err(e) = self.state.save(&self.save_path) {
+                eprintln!("Could not save: {}", e);
+            }
+        }
+
+        exit(0);
+    }
+
+    /// Has the computer decide on a thread, lays out its move once decided
+    /// and submits it once it has been visible for `BOT_DELAY`.
+    fn play_computer(&mut self) {
+        if let Some(placed_at) = self.bot_placed_at {
+            if get_time() - placed_at >= BOT_DELAY {
+                self.bot_placed_at = None;
+                self.act(Action::Submit);
+            }
+            return;
+        }
+
+        let current = self.state.current;
+        let decision = match self.thinking.take() {
+            Some(handle) if !handle.is_finished() => {
+                self.thinking = Some(handle);
+                return;
+            }
+            Some(handle) => match handle.join() {
+                Ok((bot, decision)) => {
+                    self.bots[current] = Some(bot);
+                    decision
+                }
+                Err(_) => Decision::Pass,
+            },
+            None => match self.bots.get(current) {
+                Some(Some(bot)) => {
+                    let mut bot = bot.clone();
+                    let state = self.state.clone();
+                    self.thinking = Some(thread::spawn(move || {
+                        let decision = bot.decide(&state);
+                        (bot, decision)
+                    }));
+                    return;
+                }
+                _ => Decision::Pass,
+            },
+        };
+
+        match decision {
+            Decision::Play(candidate) => {
+                if self.state.place_move(&candidate.tiles) {
+                    self.bot_placed_at = Some(get_time());
+                } else {
+                    self.act(Action::Pass);
+                }
+            }
+            Decision::Exchange(indices) => self.act(Action::Exchange(indices)),
+            Decision::Pass => self.act(Action::Pass),
+            Decision::Challenge => self.act(Action::Challenge),
+            Decision::Accept => self.act(Action::Accept),
+        }
+    }
+
+    fn act(&mut self, action: Action) {
+        let result = self.state.act(action);
+        self.view.selected_tile = SelectedTile::None;
+
+        let summary = match result {
+            Ok(Outcome::Played(score)) => score.to_string(),
+            Ok(Outcome::Passed) => "Passed".to_string(),
+            Ok(Outcome::Exchanged(count)) => format!("Exchanged {} tiles", count),
+            Ok(Outcome::Recalled) => return,
+            Ok(Outcome::Accepted) => "Accepted".to_string(),
+            Ok(Outcome::Challenged(phony)) if !phony.is_empty() => {
+                format!("Challenge upheld: {} not valid", phony.join(", "))
+            }
+            Ok(Outcome::Challenged(_)) => match self.state.challenge_rule {
+                ChallengeRule::Double => "Challenge failed, turn lost".to_string(),
+                ChallengeRule::FivePoint => format!(
+                    "Challenge failed, {} points to the last player",
+                    CHALLENGE_BONUS
+                ),
+                _ => "Challenge failed".to_string(),
+            },
+            Err(e) => {
+                self.message = Some(e.to_string());
+                return;
+            }
+        };
+        self.simulation = None;
+        self.endgame = None;
+        if !self.casual {
+            self.undo.clear();
+            self.redo.clear();
+        }
+
+        if self.state.finished {
+            self.message = None;
+            self.screen = Screen::GameOver;
+        } else if self.state.going_out().is_some() {
+            self.message = Some(format!(
+                "{}. {} can press C to challenge or Enter to accept.",
+                summary,
+                self.state.player().name
+            ));
+        } else {
+            self.message = Some(format!("{}. {}'s turn.", summary, self.state.player().name));
+        }
+    }
+
+    /// Simulates the best scoring moves for the current player in the
+    /// background, or searches the endgame once the bag is empty. Placed
+    /// tiles go back to the rack first. Progress and results are shown in
+    /// the message line.
+    fn analyse(&mut self) {
+        if self.endgame.is_some() {
+            return;
+        }
+        if self.simulation.is_some() {
+            self.simulation = None;
+            self.message = Some("Analysis stopped.".to_string());
+            return;
+        }
+
+        self.act(Action::UndoPlacement);
+
+        if self.state.tile_bag.is_empty() {
+            let state = self.state.clone();
+            let limits = EndgameLimits {
+                time: Some(ENDGAME_TIME),
+                ..EndgameLimits::default()
+            };
+            self.endgame = Some(thread::spawn(move || solve_endgame(&state, limits)));
+            self.message = Some("Searching the endgame...".to_string());
+            return;
+        }
+
+        let state = &self.state;
+        let candidates = ranked(state, SIM_CANDIDATES);
+
+        if candidates.is_empty() {
+            self.message = Some("No moves to analyse.".to_string());
+            return;
+        }
+
+        let seed = state.seed.wrapping_add(state.score as u64);
+        self.simulation = Some(Simulation::start(
+            state,
+            candidates,
+            SIM_ITERATIONS,
+            SIM_PLIES,
+            seed,
+        ));
+    }
+
+    fn poll_simulation(&mut self) {
+        let simulation = match &self.simulation {
+            Some(simulation) => simulation,
+            None => return,
+        };
+
+        if !simulation.is_finished() {
+            self.message = Some(format!(
+                "Analysing... {:.0}% (press A to stop)",
+                simulation.progress() * 100.0
+            ));
+            return;
+        }
+
+        let best: Vec<String> = simulation
+            .results()
+            .iter()
+            .take(3)
+            .map(|result| {
+                format!(
+                    "{} {} ({:+.1})",
+                    result.candidate.words()[0].to_uppercase(),
+                    result.candidate.score.total,
+                    result.mean
+                )
+            })
+            .collect();
+        self.message = Some(format!("Best moves: {}", best.join(", ")));
+        self.simulation = None;
+    }
+
+    fn poll_endgame(&mut self) {
+        if !self.endgame.as_ref().is_some_and(JoinHandle::is_finished) {
+            return;
+        }
+
+        let solution = self
+            .endgame
+            .take()
+            .and_then(|handle| handle.join().ok().flatten());
+        self.message = Some(match solution {
+            Some(solution) => {
+                let line: Vec<String> = solution
+                    .line
+                    .iter()
+                    .map(|next| match next {
+                        EndgameMove::Play(candidate) => format!(
+                            "{} {}",
+                            candidate.words()[0].to_uppercase(),
+                            candidate.score.total
+                        ),
+                        EndgameMove::Pass => "pass".to_string(),
+                    })
+                    .collect();
+                if solution.complete {
+                    format!("Solved: {} ({:+})", line.join(", "), solution.spread)
+                } else {
+                    format!(
+                        "Best line found {} moves ahead: {} ({:+})",
+                        solution.depth,
+                        line.join(", "),
+                        solution.spread
+                    )
+                }
+            }
+            None => "No endgame to solve.".to_string(),
+        });
+    }
+
+    /// Lists the best moves for the current rack. Placed tiles go back to
+    /// the rack first.
+    fn show_hints(&mut self) {
+        self.act(Action::UndoPlacement);
+
+        let hints = ranked(&self.state, HINT_COUNT);
+
+        if hints.is_empty() {
+            self.message = Some("No moves available. Pass or exchange.".to_string());
+            return;
+        }
+
+        self.hints = hints;
+        self.select_hint(0);
+    }
+
+    fn select_hint(&mut self, selected: usize) {
+        self.screen = Screen::Hint(selected);
+        self.message = Some(format!(
+            "{}. Up/Down to browse, Enter to place, Escape to close.",
+            self.hints[selected].score
+        ));
+    }
+
+    /// The previewed hint is only drawn over the board. Enter lays it out
+    /// on the board so it can still be changed before submitting.
+    fn handle_hint(&mut self, selected: usize) {
+        if is_key_pressed(KeyCode::Up) {
+            self.select_hint(selected.saturating_sub(1));
+        } else if is_key_pressed(KeyCode::Down) {
+            self.select_hint((selected + 1).min(self.hints.len() - 1));
+        } else if is_key_pressed(KeyCode::Enter) {
+            let tiles = self.hints[selected].tiles.clone();
+            self.undoable(|game| {
+                game.state.place_move(&tiles);
+            });
+            self.close_hints();
+        } else if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::H) {
+            self.close_hints();
+        }
+    }
+
+    fn handle_replay(&mut self, turns: usize) {
+        if is_key_pressed(KeyCode::Left) {
+            self.screen = Screen::Replay(turns.saturating_sub(1));
+        } else if is_key_pressed(KeyCode::Right) {
+            self.screen = Screen::Replay((turns + 1).min(self.state.history.len()));
+        } else if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::R) {
+            self.screen = if self.state.finished {
+                Screen::GameOver
+            } else {
+                Screen::Main
+            };
+        }
+    }
+
+    fn close_hints(&mut self) {
+        self.hints.clear();
+        self.message = None;
+        self.screen = Screen::Main;
+    }
+
+    /// Runs `f` and lets Ctrl+Z take back whatever it changed. Committed
+    /// turns can only be taken back in casual games.
+    fn undoable<F: FnOnce(&mut Game)>(&mut self, f: F) {
+        let before = self.state.clone();
+        f(self);
+
+        let turn_ended = self.state.history.len() != before.history.len();
+        if changed(&before, &self.state) && (self.casual || !turn_ended) {
+            self.undo.push(before);
+            self.redo.clear();
+        }
+    }
+
+    fn undo(&mut self) {
+        match self.undo.pop() {
+            Some(state) => {
+                let current = std::mem::replace(&mut self.state, state);
+                self.redo.push(current);
+                self.refresh();
+                self.message = Some("Undone. Press Ctrl+Y to redo.".to_string());
+            }
+            None => self.message = Some("Nothing to undo.".to_string()),
+        }
+    }
+
+    fn redo(&mut self) {
+        match self.redo.pop() {
+            Some(state) => {
+                let current = std::mem::replace(&mut self.state, state);
+                self.undo.push(current);
+                self.refresh();
+                self.message = None;
+            }
+            None => self.message = Some("Nothing to redo.".to_string()),
+        }
+    }
+
+    fn handle_exchange(&mut self, mut selected: [bool; RACK_SIZE]) {
+        if is_key_pressed(KeyCode::Enter) {
+            let indices = (0..selected.len()).filter(|&i| selected[i]).collect();
+            self.undoable(|game| game.act(Action::Exchange(indices)));
+        } else if is_key_pressed(KeyCode::X) {
+            self.view.selected_tile = SelectedTile::None;
+            self.message = None;
+        } else if is_mouse_button_pressed(MouseButton::Left) {
+            let mouse_pos = mouse_position();
+            let player = self.state.player();
+
+            if let Some(i) = self.view.get_rack_tile(mouse_pos.0, mouse_pos.1, player) {
+                selected[i] = !selected[i];
+                self.view.selected_tile = SelectedTile::Exchange(selected);
+            }
+        }
+    }
+
+    fn pick_blank(&mut self, row: usize, col: usize) {
+        if is_key_pressed(KeyCode::Escape) {
+            // Nothing is left to undo of placing the blank.
+            self.state.take_back(row, col);
+            self.undo.pop();
+            self.screen = Screen::Main;
+        } else if let Some(letter) = get_last_key_pressed().and_then(tile_from_key) {
+            self.state.board.assign_blank(row, col, letter);
+            self.screen = Screen::Main;
         }
     }
 
     fn remove_tile_from_board(&mut self) {
         let mouse_pos = mouse_position();
 
-        self.board
-            .remove_tile_from_board(mouse_pos.0, mouse_pos.1, &mut self.players[0]);
+        self.view
+            .remove_tile_from_board(mouse_pos.0, mouse_pos.1, &mut self.state);
     }
 
     fn swap_tile_on_board(&mut self) {
         let mouse_pos = mouse_position();
 
-        self.board.swap_tile_on_board(mouse_pos.0, mouse_pos.1);
+        self.view
+            .swap_tile_on_board(mouse_pos.0, mouse_pos.1, &mut self.state.board);
     }
 
     fn swap_tile_on_rack(&mut self) {
         let mouse_pos = mouse_position();
 
-        self.board
-            .swap_tile_on_rack(mouse_pos.0, mouse_pos.1, &mut self.players[0]);
+        let player = &mut self.state.players[self.state.current];
+        self.view
+            .swap_tile_on_rack(mouse_pos.0, mouse_pos.1, player);
     }
 
     fn place_tile(&mut self) {
         let mouse_pos = mouse_position();
-        self.board
-            .place_tile(mouse_pos.0, mouse_pos.1, &mut self.players[0]);
+        self.view
+            .place_tile(mouse_pos.0, mouse_pos.1, &mut self.state);
+
+        if let Some((row, col)) = self.state.board.unassigned_blank() {
+            self.screen = Screen::PickBlank(row, col);
+        }
     }
 
     fn select_tile_from_board(&mut self) {
         let mouse_pos = mouse_position();
-        let selected = self.board.get_board_tile(mouse_pos.0, mouse_pos.1);
+        let selected = self
+            .view
+            .get_board_tile(mouse_pos.0, mouse_pos.1, &self.state.board);
 
         if selected.is_none() {
             return;
         }
         let selected = selected.unwrap();
 
-        if let SelectedTile::Board(x, y) = self.board.selected_tile {
+        if let SelectedTile::Board(x, y) = self.view.selected_tile {
             if selected == (x, y) {
-                self.board.selected_tile = SelectedTile::None;
+                self.view.selected_tile = SelectedTile::None;
             }
         } else {
-            self.board.selected_tile = SelectedTile::Board(selected.0, selected.1);
+            self.view.selected_tile = SelectedTile::Board(selected.0, selected.1);
         }
     }
 
     fn select_tile_from_rack(&mut self) {
         let mouse_pos = mouse_position();
         let selected = self
-            .board
-            .get_rack_tile(mouse_pos.0, mouse_pos.1, &self.players[0]);
+            .view
+            .get_rack_tile(mouse_pos.0, mouse_pos.1, self.state.player());
 
         if selected.is_none() {
             return;
         }
         let selected = selected.unwrap();
 
-        if let SelectedTile::Rack(tile) = self.board.selected_tile {
+        if let SelectedTile::Rack(tile) = self.view.selected_tile {
             if selected == tile {
-                self.board.selected_tile = SelectedTile::None;
+                self.view.selected_tile = SelectedTile::None;
             }
         } else {
-            self.board.selected_tile = SelectedTile::Rack(selected);
+            self.view.selected_tile = SelectedTile::Rack(selected);
         }
     }
 }
+
+/// Whether anything a player can undo differs between `a` and `b`.
+fn changed(a: &State, b: &State) -> bool {
+    a.board.squares() != b.board.squares()
+        || a.history.len() != b.history.len()
+        || a.players
+            .iter()
+            .zip(&b.players)
+            .any(|(a, b)| a.tiles != b.tiles || a.score != b.score)
+}
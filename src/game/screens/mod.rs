@@ -1,7 +1,7 @@
 use crate::game::TAN;
 use crate::Game;
 use macroquad::prelude::*;
-use std::process::exit;
+use scrabble::engine::{ChallengeRule, Controller, Level, Play, Player};
 
 pub const BACKGROUND: Color = color_u8!(43, 42, 51, 255);
 
@@ -10,6 +10,19 @@ pub enum Screen {
     Main,
     Exit,
     Start,
+    /// Naming the players, with the index of the seat being edited.
+    Setup(usize),
+    PickBlank(usize, usize),
+    /// Waiting for the next player in a hot-seat game, with every rack
+    /// hidden.
+    Handover,
+    /// Browsing the hints, with the index of the one shown on the board.
+    Hint(usize),
+    /// Stepping through the history, with the number of turns shown.
+    Replay(usize),
+    GameOver,
+    /// Leaving the game, which saves it first.
+    Quit,
 }
 
 impl Screen {
@@ -17,23 +30,78 @@ impl Screen {
         use Screen::*;
 
         match *self {
-            Main => game.board.draw(&game.players[0]),
-            Exit => {
-                if Screen::confirm_exit() {
-                    return Main;
+            Main => {
+                let empty = Player::new();
+                game.view.draw(
+                    &game.state.board,
+                    game.shown_rack().unwrap_or(&empty),
+                    game.message.as_deref(),
+                );
+                game.view
+                    .draw_history(&game.state.players, &game.state.history, None);
+                game.view.draw_hud(&game.state);
+                Screen::draw_seed(game.state.seed);
+            }
+            Exit => return Screen::confirm_exit(),
+            Start => Screen::draw_start(
+                game.level,
+                game.state.challenge_rule,
+                game.casual,
+                game.save_path.exists(),
+            ),
+            Setup(selected) => Screen::draw_setup(&game.seats, selected),
+            Handover => {
+                let message = format!(
+                    "Pass the device to {} - press any key.",
+                    game.state.player().name
+                );
+                game.view
+                    .draw(&game.state.board, &Player::new(), Some(&message));
+                game.view
+                    .draw_history(&game.state.players, &game.state.history, None);
+                game.view.draw_hud(&game.state);
+                Screen::draw_seed(game.state.seed);
+            }
+            PickBlank(..) => {
+                game.view.draw(
+                    &game.state.board,
+                    game.state.player(),
+                    Some("Type the letter for the blank tile."),
+                );
+                game.view
+                    .draw_history(&game.state.players, &game.state.history, None);
+                game.view.draw_hud(&game.state);
+                Screen::draw_seed(game.state.seed);
+            }
+            Hint(selected) => {
+                game.view.draw(
+                    &game.state.board,
+                    game.state.player(),
+                    game.message.as_deref(),
+                );
+                if let Some(hint) = game.hints.get(selected) {
+                    game.view.draw_preview(&hint.tiles);
                 }
+                game.view.draw_hints(&game.hints, selected);
+                game.view
+                    .draw_history(&game.state.players, &game.state.history, None);
+                game.view.draw_hud(&game.state);
+                Screen::draw_seed(game.state.seed);
             }
-            Start => {
-                if Screen::draw_start() {
-                    return Main;
+            Replay(turns) => Screen::draw_replay(game, turns),
+            GameOver => {
+                if Screen::draw_game_over(game) {
+                    return Quit;
                 }
             }
+            Quit => {}
         }
 
         *self
     }
 
-    pub fn confirm_exit() -> bool {
+    /// Asks before quitting and returns the next screen.
+    pub fn confirm_exit() -> Screen {
         clear_background(BACKGROUND);
         let x = if screen_height() > screen_width() {
             screen_width()
@@ -75,13 +143,15 @@ impl Screen {
         );
 
         if is_key_pressed(KeyCode::Escape) {
-            exit(0);
+            Screen::Quit
+        } else if get_last_key_pressed().is_some() {
+            Screen::Main
+        } else {
+            Screen::Exit
         }
-
-        matches!(get_last_key_pressed(), Some(_))
     }
 
-    pub fn draw_start() -> bool {
+    pub fn draw_start(level: Level, rule: ChallengeRule, casual: bool, has_save: bool) {
         clear_background(BACKGROUND);
         let x = if screen_height() > screen_width() {
             screen_width()
@@ -91,8 +161,6 @@ impl Screen {
 
         let top_text = "Welcome to Scrabble!";
         let top_text_size = measure_text(top_text, None, x as u16 / 10, 1.0);
-        let bottom_text = "Press any key to start.";
-        let bottom_text_size = measure_text(bottom_text, None, x as u16 / 20, 1.0);
 
         draw_text(
             top_text,
@@ -102,14 +170,174 @@ impl Screen {
             TAN,
         );
 
-        draw_text(
-            bottom_text,
-            (screen_width() - bottom_text_size.width) / 2.0,
-            ((screen_height() - bottom_text_size.height) / 2.0) + x / 20.0,
-            x / 20.0,
+        let mut options = vec![
+            "Press 1 for two players.".to_string(),
+            "Press 2 to play against the computer.".to_string(),
+            "Press 3 to let the computer go first.".to_string(),
+            format!("Press L to change the computer level: {}", level),
+            format!("Press V to change the challenge rule: {}", rule),
+            format!(
+                "Press C to allow undoing whole turns: {}",
+                if casual { "Yes" } else { "No" }
+            ),
+        ];
+        if has_save {
+            options.push("Press 4 to resume the saved game.".to_string());
+        }
+        options.push("Press N to set up a game for up to 4 players.".to_string());
+
+        for (i, text) in options.iter().enumerate() {
+            let text_size = measure_text(text, None, x as u16 / 20, 1.0);
+            draw_text(
+                text,
+                (screen_width() - text_size.width) / 2.0,
+                ((screen_height() - text_size.height) / 2.0) + (i + 1) as f32 * x / 20.0,
+                x / 20.0,
+                TAN,
+            );
+        }
+    }
+
+    /// Lists the seats with the one at `selected` in gold.
+    pub fn draw_setup(seats: &[Player], selected: usize) {
+        clear_background(BACKGROUND);
+        let x = if screen_height() > screen_width() {
+            screen_width()
+        } else {
+            screen_height()
+        };
+
+        let x = x / 20.0;
+
+        let mut lines = vec![("Players".to_string(), TAN)];
+        for (i, seat) in seats.iter().enumerate() {
+            let controller = match seat.controller {
+                Controller::Human => "Human".to_string(),
+                Controller::Computer(level) => format!("Computer ({})", level),
+            };
+            let (cursor, color) = if i == selected {
+                ("_", GOLD)
+            } else {
+                ("", TAN)
+            };
+            lines.push((
+                format!("{}. {}{} - {}", i + 1, seat.name, cursor, controller),
+                color,
+            ));
+        }
+        lines.push((String::new(), TAN));
+        lines.push((
+            "Type a name, Up/Down pick a seat, Left/Right switch human or computer.".to_string(),
+            TAN,
+        ));
+        lines.push((
+            "Tab adds a player, Delete removes one, Enter starts, Escape goes back.".to_string(),
             TAN,
+        ));
+
+        let top = (screen_height() - lines.len() as f32 * x) / 2.0;
+        for (i, (line, color)) in lines.iter().enumerate() {
+            let size = if i == 0 { x } else { x / 2.0 };
+            let text_size = measure_text(line, None, size as u16, 1.0);
+            draw_text(
+                line,
+                (screen_width() - text_size.width) / 2.0,
+                top + i as f32 * x,
+                size,
+                *color,
+            );
+        }
+    }
+
+    /// Draws the board after `turns` turns with the last of them outlined,
+    /// and the rack the next turn was played from.
+    pub fn draw_replay(game: &Game, turns: usize) {
+        let history = &game.state.history;
+        let board = game.state.board_after(turns);
+
+        let mut player = Player::new();
+        player.tiles = match history.get(turns) {
+            // Other people's racks stay hidden until a hot-seat game is over.
+            Some(record)
+                if game.hot_seat()
+                    && !game.state.finished
+                    && record.player != game.state.current =>
+            {
+                vec![]
+            }
+            Some(record) => record.rack.clone(),
+            None => game
+                .shown_rack()
+                .map_or_else(Vec::new, |player| player.tiles.clone()),
+        };
+
+        let scores: Vec<String> = game
+            .state
+            .scores_after(turns)
+            .iter()
+            .map(|score| score.to_string())
+            .collect();
+        let message = format!(
+            "Turn {} of {}, scores {}. Left/Right to step, Escape to return.",
+            turns,
+            history.len(),
+            scores.join(" - ")
         );
 
-        get_last_key_pressed().is_some()
+        game.view.draw(&board, &player, Some(&message));
+        if let Some(Play::Place { tiles, .. }) = turns.checked_sub(1).map(|i| &history[i].play) {
+            game.view.draw_preview(tiles);
+        }
+        game.view
+            .draw_history(&game.state.players, history, turns.checked_sub(1));
+        Screen::draw_seed(game.state.seed);
+    }
+
+    pub fn draw_seed(seed: u64) {
+        let x = if screen_height() > screen_width() {
+            screen_width()
+        } else {
+            screen_height()
+        };
+
+        let x = x / 40.0;
+
+        draw_text(&format!("Seed: {}", seed), x, screen_height() - x, x, TAN);
+    }
+
+    /// Returns whether the player asked to exit.
+    pub fn draw_game_over(game: &Game) -> bool {
+        clear_background(BACKGROUND);
+        let x = if screen_height() > screen_width() {
+            screen_width()
+        } else {
+            screen_height()
+        };
+
+        let x = x / 20.0;
+
+        let mut standings: Vec<&Player> = game.state.players.iter().collect();
+        standings.sort_by_key(|player| std::cmp::Reverse(player.score));
+
+        let mut lines = vec!["Game Over".to_string()];
+        for (place, player) in standings.iter().enumerate() {
+            lines.push(format!("{}. {}: {}", place + 1, player.name, player.score));
+        }
+        lines.push(format!("Seed: {}", game.state.seed));
+        lines.push("Press R to replay the game or Escape to exit.".to_string());
+
+        let top = (screen_height() - lines.len() as f32 * x) / 2.0;
+        for (i, line) in lines.iter().enumerate() {
+            let size = measure_text(line, None, x as u16, 1.0);
+            draw_text(
+                line,
+                (screen_width() - size.width) / 2.0,
+                top + i as f32 * x,
+                x,
+                TAN,
+            );
+        }
+
+        is_key_pressed(KeyCode::Escape)
     }
 }
@@ -0,0 +1,4 @@
+//! Scrabble rules without any rendering: board state, move validation,
+//! scoring, the tile bag and turn order.
+
+pub mod engine;
@@ -1,11 +1,24 @@
 use macroquad::prelude::*;
+use scrabble::engine::LeaveTable;
+use std::path::PathBuf;
+
+/// Where games are saved unless `--save` says otherwise.
+const SAVE_FILE: &str = "scrabble.save";
+/// Where games are exported and imported as GCG unless `--gcg` says otherwise.
+const GCG_FILE: &str = "scrabble.gcg";
 
 mod game;
 use game::*;
 
 #[macroquad::main(window_conf)]
 async fn main() {
-    let mut game = Game::new();
+    let args = Args::parse();
+    let mut game = Game::new(
+        args.seed,
+        args.leaves.unwrap_or_else(LeaveTable::standard),
+        args.save.unwrap_or_else(|| PathBuf::from(SAVE_FILE)),
+        args.gcg.unwrap_or_else(|| PathBuf::from(GCG_FILE)),
+    );
 
     loop {
         game.play();
@@ -22,3 +35,43 @@ fn window_conf() -> Conf {
         ..Default::default()
     }
 }
+
+#[derive(Default)]
+struct Args {
+    seed: Option<u64>,
+    leaves: Option<LeaveTable>,
+    save: Option<PathBuf>,
+    gcg: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse() -> Args {
+        let mut args = Args::default();
+        let mut iter = std::env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--seed" => match iter.next().map(|seed| seed.parse()) {
+                    Some(Ok(seed)) => args.seed = Some(seed),
+                    _ => eprintln!("--seed expects a number"),
+                },
+                "--leaves" => match iter.next().map(LeaveTable::load) {
+                    Some(Ok(leaves)) => args.leaves = Some(leaves),
+                    Some(Err(e)) => eprintln!("Could not load leaves: {}", e),
+                    None => eprintln!("--leaves expects a file"),
+                },
+                "--save" => match iter.next() {
+                    Some(path) => args.save = Some(PathBuf::from(path)),
+                    None => eprintln!("--save expects a file"),
+                },
+                "--gcg" => match iter.next() {
+                    Some(path) => args.gcg = Some(PathBuf::from(path)),
+                    None => eprintln!("--gcg expects a file"),
+                },
+                _ => eprintln!("Unknown argument: {}", arg),
+            }
+        }
+
+        args
+    }
+}